@@ -0,0 +1,579 @@
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+use rug::{integer::Order, ops::Pow, Complete, Integer};
+
+/// Arbitrary precision integer operations needed by the crate
+// Implemented for rug::Integer, other backends (pure Rust, constant time...) can be plugged in
+// by implementing this trait
+pub trait BigInteger: Sized + Clone + PartialEq + PartialOrd {
+    /// Conversion from a machine integer
+    fn from_i64(v: i64) -> Self;
+
+    /// Conversion to a machine integer, if it fits
+    fn to_i64(&self) -> Option<i64>;
+
+    /// Whether the integer is zero
+    fn is_zero(&self) -> bool;
+
+    /// Whether the integer is odd
+    fn is_odd(&self) -> bool;
+
+    /// Whether the integer is negative
+    fn is_negative(&self) -> bool;
+
+    /// Absolute value
+    fn abs(&self) -> Self;
+
+    /// Addition
+    fn add(&self, y: &Self) -> Self;
+
+    /// Subtraction
+    fn sub(&self, y: &Self) -> Self;
+
+    /// Multiplication
+    fn mul(&self, y: &Self) -> Self;
+
+    /// Quotient rounded towards zero
+    fn div(&self, y: &Self) -> Self;
+
+    /// Power
+    fn pow(&self, e: u32) -> Self;
+
+    /// Remainder in [0, |m|)
+    fn modulo(&self, m: &Self) -> Self;
+
+    /// Right shift of |self|
+    fn shr(&self, bits: u32) -> Self;
+
+    /// Modular power, None if the exponent is negative and self is not invertible
+    fn pow_mod(&self, e: &Self, m: &Self) -> Option<Self>;
+
+    /// Modular inverse, None if self is not invertible
+    fn invert_mod(&self, m: &Self) -> Option<Self>;
+
+    /// Number of bits of |self|
+    fn significant_bits(&self) -> u32;
+
+    /// Bit i of |self|
+    fn get_bit(&self, i: u32) -> bool;
+
+    /// Legendre symbol (self / p) for an odd prime p
+    fn legendre(&self, p: &Self) -> i32;
+}
+
+impl BigInteger for Integer {
+    fn from_i64(v: i64) -> Self {
+        Integer::from(v)
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        Integer::to_i64(self)
+    }
+
+    fn is_zero(&self) -> bool {
+        Integer::is_zero(self)
+    }
+
+    fn is_odd(&self) -> bool {
+        Integer::is_odd(self)
+    }
+
+    fn is_negative(&self) -> bool {
+        Integer::is_negative(self)
+    }
+
+    fn abs(&self) -> Self {
+        self.abs_ref().complete()
+    }
+
+    fn add(&self, y: &Self) -> Self {
+        (self + y).complete()
+    }
+
+    fn sub(&self, y: &Self) -> Self {
+        (self - y).complete()
+    }
+
+    fn mul(&self, y: &Self) -> Self {
+        (self * y).complete()
+    }
+
+    fn div(&self, y: &Self) -> Self {
+        (self / y).complete()
+    }
+
+    fn pow(&self, e: u32) -> Self {
+        Pow::pow(self, e).complete()
+    }
+
+    fn modulo(&self, m: &Self) -> Self {
+        self.modulo_ref(&m.abs_ref().complete()).complete()
+    }
+
+    fn shr(&self, bits: u32) -> Self {
+        self.abs_ref().complete() >> bits
+    }
+
+    fn pow_mod(&self, e: &Self, m: &Self) -> Option<Self> {
+        self.pow_mod_ref(e, m).map(Integer::from)
+    }
+
+    fn invert_mod(&self, m: &Self) -> Option<Self> {
+        self.invert_ref(m).map(Integer::from)
+    }
+
+    fn significant_bits(&self) -> u32 {
+        Integer::significant_bits(self)
+    }
+
+    fn get_bit(&self, i: u32) -> bool {
+        if Integer::is_negative(self) {
+            self.abs_ref().complete().get_bit(i)
+        } else {
+            Integer::get_bit(self, i)
+        }
+    }
+
+    fn legendre(&self, p: &Self) -> i32 {
+        Integer::legendre(self, p)
+    }
+}
+
+// Big endian bytes of |n|, left padded with zeros to len bytes
+// len must be at least n.significant_digits::<u8>(). Unlike Integer::to_digits, this does not
+// need the std feature of rug
+pub(crate) fn to_be_bytes(n: &Integer, len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    n.write_digits(&mut bytes, Order::Msf);
+    bytes
+}
+
+/// Pure Rust arbitrary precision signed integer
+// Sign and magnitude, the magnitude being little endian 64-bit limbs without trailing zero limbs
+// Zero has no limbs and is not negative
+// Only meant as a fallback when rug is not available: the algorithms are the schoolbook ones
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u64>,
+}
+
+impl BigInt {
+    /// Zero
+    pub fn zero() -> Self {
+        BigInt::default()
+    }
+
+    // Integer with the given sign and magnitude, normalized
+    fn from_parts(negative: bool, mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        let negative = negative && !limbs.is_empty();
+        BigInt { negative, limbs }
+    }
+
+    /// Whether the integer is zero
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Whether the integer is odd
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|l| l & 1 == 1)
+    }
+
+    /// Whether the integer is positive
+    pub fn is_positive(&self) -> bool {
+        !self.negative && !self.is_zero()
+    }
+
+    /// Addition
+    pub fn add(&self, y: &Self) -> Self {
+        if self.negative == y.negative {
+            return BigInt::from_parts(self.negative, add_mag(&self.limbs, &y.limbs));
+        }
+        match cmp_mag(&self.limbs, &y.limbs) {
+            Ordering::Less => BigInt::from_parts(y.negative, sub_mag(&y.limbs, &self.limbs)),
+            _ => BigInt::from_parts(self.negative, sub_mag(&self.limbs, &y.limbs)),
+        }
+    }
+
+    /// Subtraction
+    pub fn sub(&self, y: &Self) -> Self {
+        self.add(&y.neg())
+    }
+
+    /// Additive inverse
+    pub fn neg(&self) -> Self {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+
+    /// Multiplication
+    pub fn mul(&self, y: &Self) -> Self {
+        BigInt::from_parts(self.negative != y.negative, mul_mag(&self.limbs, &y.limbs))
+    }
+
+    /// Quotient rounded towards zero
+    /// /!\ Panics if y is zero
+    pub fn div(&self, y: &Self) -> Self {
+        let (q, _) = divrem_mag(&self.limbs, &y.limbs);
+        BigInt::from_parts(self.negative != y.negative, q)
+    }
+
+    /// Remainder in [0, |m|)
+    /// /!\ Panics if m is zero
+    pub fn modulo(&self, m: &Self) -> Self {
+        let (_, r) = divrem_mag(&self.limbs, &m.limbs);
+        let r = BigInt::from_parts(false, r);
+        if self.negative && !r.is_zero() {
+            BigInt::from_parts(false, sub_mag(&m.limbs, &r.limbs))
+        } else {
+            r
+        }
+    }
+
+    /// Power
+    pub fn pow(&self, e: u32) -> Self {
+        let mut res = BigInt::from_i64(1);
+        for i in (0..u32::BITS - e.leading_zeros()).rev() {
+            res = res.mul(&res);
+            if (e >> i) & 1 == 1 {
+                res = res.mul(self);
+            }
+        }
+        res
+    }
+
+    /// Little endian bit expansion of |self|
+    pub fn to_bits(&self) -> Vec<bool> {
+        (0..BigInteger::significant_bits(self))
+            .map(|i| BigInteger::get_bit(self, i))
+            .collect()
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, y: &Self) -> Option<Ordering> {
+        Some(self.cmp(y))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, y: &Self) -> Ordering {
+        match (self.negative, y.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.limbs, &y.limbs),
+            (true, true) => cmp_mag(&y.limbs, &self.limbs),
+        }
+    }
+}
+
+impl BigInteger for BigInt {
+    fn from_i64(v: i64) -> Self {
+        BigInt::from_parts(v < 0, vec![v.unsigned_abs()])
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [l] if self.negative => 0i64.checked_sub_unsigned(*l),
+            [l] => i64::try_from(*l).ok(),
+            _ => None,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        BigInt::is_zero(self)
+    }
+
+    fn is_odd(&self) -> bool {
+        BigInt::is_odd(self)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    fn abs(&self) -> Self {
+        BigInt::from_parts(false, self.limbs.clone())
+    }
+
+    fn add(&self, y: &Self) -> Self {
+        BigInt::add(self, y)
+    }
+
+    fn sub(&self, y: &Self) -> Self {
+        BigInt::sub(self, y)
+    }
+
+    fn mul(&self, y: &Self) -> Self {
+        BigInt::mul(self, y)
+    }
+
+    fn div(&self, y: &Self) -> Self {
+        BigInt::div(self, y)
+    }
+
+    fn pow(&self, e: u32) -> Self {
+        BigInt::pow(self, e)
+    }
+
+    fn modulo(&self, m: &Self) -> Self {
+        BigInt::modulo(self, m)
+    }
+
+    fn shr(&self, bits: u32) -> Self {
+        BigInt::from_parts(false, shr_mag(&self.limbs, bits))
+    }
+
+    fn pow_mod(&self, e: &Self, m: &Self) -> Option<Self> {
+        let base = if e.negative {
+            self.invert_mod(m)?
+        } else {
+            BigInt::modulo(self, m)
+        };
+        let mut res = BigInt::modulo(&BigInt::from_i64(1), m);
+        for i in (0..BigInteger::significant_bits(e)).rev() {
+            res = BigInt::modulo(&res.mul(&res), m);
+            if BigInteger::get_bit(e, i) {
+                res = BigInt::modulo(&res.mul(&base), m);
+            }
+        }
+        Some(res)
+    }
+
+    fn invert_mod(&self, m: &Self) -> Option<Self> {
+        // Extended Euclid: r = s self mod m
+        let m = BigInteger::abs(m);
+        let (mut r0, mut r1) = (m.clone(), BigInt::modulo(self, &m));
+        let (mut s0, mut s1) = (BigInt::zero(), BigInt::from_i64(1));
+        while !r1.is_zero() {
+            let q = r0.div(&r1);
+            let r2 = r0.sub(&q.mul(&r1));
+            let s2 = s0.sub(&q.mul(&s1));
+            (r0, r1) = (r1, r2);
+            (s0, s1) = (s1, s2);
+        }
+        if r0 != BigInt::from_i64(1) {
+            return None;
+        }
+        Some(BigInt::modulo(&s0, &m))
+    }
+
+    fn significant_bits(&self) -> u32 {
+        match self.limbs.last() {
+            None => 0,
+            Some(l) => 64 * (self.limbs.len() as u32 - 1) + (u64::BITS - l.leading_zeros()),
+        }
+    }
+
+    fn get_bit(&self, i: u32) -> bool {
+        self.limbs
+            .get((i / 64) as usize)
+            .is_some_and(|l| (l >> (i % 64)) & 1 == 1)
+    }
+
+    fn legendre(&self, p: &Self) -> i32 {
+        // Euler's criterion
+        let e = p.sub(&BigInt::from_i64(1)).shr(1);
+        let res = self.pow_mod(&e, p).unwrap_or_default();
+        if res.is_zero() {
+            0
+        } else if res == BigInt::from_i64(1) {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
+// Comparison of magnitudes
+fn cmp_mag(a: &[u64], b: &[u64]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+// |a| + |b|
+fn add_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut res = Vec::with_capacity(a.len() + 1);
+    let mut carry = false;
+    for (i, x) in a.iter().enumerate() {
+        let (s, c1) = x.overflowing_add(*b.get(i).unwrap_or(&0));
+        let (s, c2) = s.overflowing_add(carry as u64);
+        res.push(s);
+        carry = c1 || c2;
+    }
+    if carry {
+        res.push(1);
+    }
+    res
+}
+
+// |a| - |b|, for |a| >= |b|
+fn sub_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut res = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, x) in a.iter().enumerate() {
+        let (d, b1) = x.overflowing_sub(*b.get(i).unwrap_or(&0));
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        res.push(d);
+        borrow = b1 || b2;
+    }
+    res
+}
+
+// |a| |b|
+fn mul_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut res = vec![0u64; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, y) in b.iter().enumerate() {
+            let t = (*x as u128) * (*y as u128) + res[i + j] as u128 + carry;
+            res[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        res[i + b.len()] = carry as u64;
+    }
+    res
+}
+
+// |a| >> bits
+fn shr_mag(a: &[u64], bits: u32) -> Vec<u64> {
+    let limbs = (bits / 64) as usize;
+    let bits = bits % 64;
+    if limbs >= a.len() {
+        return vec![];
+    }
+    let a = &a[limbs..];
+    (0..a.len())
+        .map(|i| {
+            let hi = if bits == 0 {
+                0
+            } else {
+                a.get(i + 1).map_or(0, |h| h << (64 - bits))
+            };
+            (a[i] >> bits) | hi
+        })
+        .collect()
+}
+
+// (|a| / |b|, |a| mod |b|), by binary long division
+fn divrem_mag(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(!b.is_empty(), "division by zero");
+    let mut q = vec![0u64; a.len()];
+    let mut r: Vec<u64> = vec![];
+    for i in (0..64 * a.len()).rev() {
+        // r = 2 r + bit i of a
+        r = add_mag(&r, &r);
+        if (a[i / 64] >> (i % 64)) & 1 == 1 {
+            r = add_mag(&r, &[1]);
+        }
+        while r.last() == Some(&0) {
+            r.pop();
+        }
+        if cmp_mag(&r, b) != Ordering::Less {
+            r = sub_mag(&r, b);
+            while r.last() == Some(&0) {
+                r.pop();
+            }
+            q[i / 64] |= 1 << (i % 64);
+        }
+    }
+    (q, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_big(a: &Integer) -> BigInt {
+        let mut limbs = vec![0; a.significant_digits::<u64>()];
+        a.write_digits(&mut limbs, Order::Lsf);
+        BigInt::from_parts(a.is_negative(), limbs)
+    }
+
+    fn to_rug(a: &BigInt) -> Integer {
+        let v = Integer::from_digits(&a.limbs, Order::Lsf);
+        if a.negative {
+            -v
+        } else {
+            v
+        }
+    }
+
+    // Single and multi-limb operands of both signs
+    fn operands() -> Vec<Integer> {
+        let mut values: Vec<Integer> = [0i64, 1, 2, 7, 255, 65_537, i64::MAX]
+            .into_iter()
+            .map(Integer::from)
+            .collect();
+        values.push(Integer::from(u64::MAX));
+        values.push((Integer::from(1) << 130) + 12_345);
+        values.push(Integer::from(3).pow(100u32));
+        let negated: Vec<Integer> = values.iter().map(|v| -v.clone()).collect();
+        values.extend(negated);
+        values
+    }
+
+    #[test]
+    fn bigint_matches_rug() {
+        for a in operands() {
+            let x = to_big(&a);
+            assert_eq!(to_rug(&x), a);
+            assert_eq!(x.to_i64(), a.to_i64());
+            assert_eq!(x.is_zero(), a.is_zero());
+            assert_eq!(x.is_odd(), a.is_odd());
+            assert_eq!(BigInteger::is_negative(&x), a.is_negative());
+            assert_eq!(to_rug(&BigInteger::abs(&x)), a.clone().abs());
+            assert_eq!(to_rug(&BigInteger::shr(&x, 67)), a.clone().abs() >> 67);
+            assert_eq!(to_rug(&BigInteger::pow(&x, 3)), a.clone().pow(3u32));
+            assert_eq!(x.significant_bits(), a.significant_bits());
+            for i in 0..200 {
+                assert_eq!(x.get_bit(i), a.clone().abs().get_bit(i));
+            }
+
+            for b in operands() {
+                let y = to_big(&b);
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                assert_eq!(to_rug(&x.add(&y)), a.clone() + &b);
+                assert_eq!(to_rug(&x.sub(&y)), a.clone() - &b);
+                assert_eq!(to_rug(&x.mul(&y)), a.clone() * &b);
+                if !b.is_zero() {
+                    assert_eq!(to_rug(&x.div(&y)), a.clone() / &b);
+                    assert_eq!(to_rug(&x.modulo(&y)), BigInteger::modulo(&a, &b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bigint_modular_operations() {
+        let p = Integer::from(103);
+        let big_p = (Integer::from(1) << 127) - 1;
+        for m in [p, big_p] {
+            for a in operands() {
+                let (x, n) = (to_big(&a), to_big(&m));
+                assert_eq!(
+                    x.invert_mod(&n).map(|v| to_rug(&v)),
+                    BigInteger::invert_mod(&a, &m)
+                );
+                for e in [Integer::from(0), Integer::from(5), Integer::from(-3)] {
+                    assert_eq!(
+                        x.pow_mod(&to_big(&e), &n).map(|v| to_rug(&v)),
+                        BigInteger::pow_mod(&a, &e, &m)
+                    );
+                }
+                assert_eq!(x.legendre(&n), a.legendre(&m));
+            }
+        }
+        // 206 = 2 * 103 is not invertible mod 103
+        assert_eq!(
+            BigInt::from_i64(206).invert_mod(&BigInt::from_i64(103)),
+            None
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use alloc::{vec, vec::Vec};
+use rug::{integer::Order, Integer};
+
+use crate::{
+    elliptic_curve::{ECPoint, EllipticCurve},
+    errors::ErrorKind,
+    field::Field,
+    hash::{expand_message_xmd, Sha256},
+    pairings::{multi_pairing, pairing_check},
+};
+
+// Domain separation tag of the secret key derivation, the salt of the IETF BLS KeyGen
+const KEYGEN_DST: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+
+/// BLS signature scheme
+// Signatures are H(m)^sk in G1, public keys are [sk]G2, and a signature is valid when
+// e(H(m), pk) = e(sig, G2), checked with the reduced Tate pairing
+// /!\ Example implementation: key generation is deterministic from the input key material,
+// and nothing here is constant time
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bls<F: Field + Clone> {
+    curve: EllipticCurve<F>,
+    g2: ECPoint<F>,
+    order: Integer,
+    embedding_degree: u32,
+    dst: Vec<u8>,
+}
+
+impl<F: Field + Clone + PartialEq> Bls<F> {
+    // New signature scheme on the subgroup of the given order
+    // g2 generates the public key group, messages are hashed to the curve with the domain
+    // separation tag dst and multiplied by the cofactor, which are set on the curve along with
+    // the order
+    pub fn new(
+        curve: EllipticCurve<F>,
+        g2: ECPoint<F>,
+        order: Integer,
+        cofactor: Integer,
+        embedding_degree: u32,
+        dst: &[u8],
+    ) -> Self {
+        Bls {
+            curve: curve.with_order(order.clone()).with_cofactor(cofactor),
+            g2,
+            order,
+            embedding_degree,
+            dst: dst.to_vec(),
+        }
+    }
+
+    // Key pair (sk, pk) derived from the key material ikm
+    // sk = expand_message_xmd(ikm || ctr, L) mod n for the first counter giving a non-zero key,
+    // and pk = [sk]G2. L = ceil((log2(n) + 128) / 8) bytes are reduced, for a bias of at most
+    // 2^-128
+    pub fn keygen(&self, ikm: &[u8]) -> Result<(Integer, ECPoint<F>), ErrorKind> {
+        if self.order <= 1 {
+            return Err(ErrorKind::InvalidInput("Group order must be larger than 1"));
+        }
+
+        let l = (self.order.significant_bits() as usize + 128).div_ceil(8);
+        let mut ctr: u32 = 0;
+        loop {
+            let data = [ikm, &ctr.to_be_bytes()].concat();
+            let okm = expand_message_xmd::<Sha256>(&data, KEYGEN_DST, l)?;
+            let sk = Integer::from_digits(&okm, Order::Msf) % &self.order;
+            if sk != 0 {
+                let pk = self.curve.mul(&sk, &self.g2)?;
+                return Ok((sk, pk));
+            }
+            ctr += 1;
+        }
+    }
+
+    // Signature [sk]H(m)
+    pub fn sign(&self, sk: &Integer, msg: &[u8]) -> Result<ECPoint<F>, ErrorKind> {
+        let pt_h = self.curve.hash_to_curve(msg, &self.dst)?;
+        self.curve.mul(sk, &pt_h)
+    }
+
+    // Whether pt is a non-zero point of the curve in the subgroup of order n
+    // The zero point must be rejected, as pairing_check skips the pairs involving it
+    fn is_valid_point(&self, pt: &ECPoint<F>) -> Result<bool, ErrorKind> {
+        Ok(*pt != ECPoint::PointAtInfinity
+            && self.curve.is_on_curve(pt)
+            && self.curve.is_in_subgroup(pt)?)
+    }
+
+    // Checks that e(H(m), pk) = e(sig, G2)
+    // The signature is rejected unless pk and sig are valid points, see is_valid_point
+    pub fn verify(&self, pk: &ECPoint<F>, msg: &[u8], sig: &ECPoint<F>) -> Result<bool, ErrorKind> {
+        if !self.is_valid_point(pk)? || !self.is_valid_point(sig)? {
+            return Ok(false);
+        }
+
+        let pt_h = self.curve.hash_to_curve(msg, &self.dst)?;
+        pairing_check(
+            &self.curve,
+            &pt_h,
+            pk,
+            sig,
+            &self.g2,
+            &self.order,
+            self.embedding_degree,
+        )
+    }
+
+    // Aggregate signature, the sum of the signatures
+    pub fn aggregate(&self, sigs: &[ECPoint<F>]) -> Result<ECPoint<F>, ErrorKind> {
+        sigs.iter().try_fold(ECPoint::PointAtInfinity, |acc, sig| {
+            self.curve.add(&acc, sig)
+        })
+    }
+
+    // Checks an aggregate signature of the messages m_i under the keys pk_i,
+    // i.e. that Π e(H(m_i), pk_i) = e(sig, G2)
+    // The signature is rejected unless all the pk_i and sig are valid points, see is_valid_point
+    // /!\ The messages must be distinct, otherwise rogue key attacks are possible
+    pub fn verify_aggregate(
+        &self,
+        pks: &[ECPoint<F>],
+        msgs: &[&[u8]],
+        sig: &ECPoint<F>,
+    ) -> Result<bool, ErrorKind> {
+        if pks.len() != msgs.len() {
+            return Err(ErrorKind::InvalidInput(
+                "There must be as many public keys as messages",
+            ));
+        }
+        if (1..msgs.len()).any(|i| msgs[..i].contains(&msgs[i])) {
+            return Err(ErrorKind::InvalidInput("Messages must be distinct"));
+        }
+        for pt in pks.iter().chain([sig]) {
+            if !self.is_valid_point(pt)? {
+                return Ok(false);
+            }
+        }
+
+        // Π e(H(m_i), pk_i) e(-sig, G2) = 1
+        let mut pairs = vec![];
+        for (pk, msg) in pks.iter().zip(msgs) {
+            let pt_h = self.curve.hash_to_curve(msg, &self.dst)?;
+            pairs.push((pt_h, pk.clone()));
+        }
+        pairs.push((self.curve.invert(sig)?, self.g2.clone()));
+
+        let res = multi_pairing(&self.curve, &pairs, &self.order, self.embedding_degree)?;
+        Ok(res.is_one())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fields::{Fp, Fp2};
+
+    type K = Fp2<103>;
+
+    // y² = x³ + x over F103, with 104 = 8 × 13 points and embedding degree 2
+    // G2 is the image of the point (3, ..) of order 13 under the distortion map (x, y) ↦ (-x, iy)
+    fn scheme() -> Bls<K> {
+        let curve = EllipticCurve::new((
+            K::zero(),
+            K::zero(),
+            K::zero(),
+            K::one(),
+            K::zero(),
+            K::zero(),
+        ))
+        .unwrap();
+        let x = Fp::<103>::new(3);
+        let y = x.clone().square().add(&Fp::one()).mul(&x).sqrt();
+        let pt = curve.point(Fp2(x, Fp::zero()), Fp2(y, Fp::zero())).unwrap();
+        let pt = curve.mul(&Integer::from(8), &pt).unwrap();
+        let g2 = match pt {
+            ECPoint::AffinePoint(x, y) => ECPoint::AffinePoint(x.neg(), y.mul(&K::new(0, 1))),
+            ECPoint::PointAtInfinity => unreachable!(),
+        };
+        Bls::new(
+            curve,
+            g2,
+            Integer::from(13),
+            Integer::from(8),
+            2,
+            b"BLS-TEST",
+        )
+    }
+
+    // Messages hashed to distinct points of the group of order 13 over F103, rather than into
+    // the group of G2 where the pairing of the toy curve is degenerate
+    const MSGS: [&[u8]; 3] = [b"m0", b"m2", b"m5"];
+
+    #[test]
+    fn sign_and_verify() {
+        let bls = scheme();
+        let (sk, pk) = bls.keygen(b"key material").unwrap();
+        let (_, other_pk) = bls.keygen(b"other key material").unwrap();
+        for (i, msg) in MSGS.iter().enumerate() {
+            let sig = bls.sign(&sk, msg).unwrap();
+            assert!(bls.verify(&pk, msg, &sig).unwrap());
+            assert!(!bls.verify(&pk, MSGS[(i + 1) % 3], &sig).unwrap());
+            assert!(!bls.verify(&other_pk, msg, &sig).unwrap());
+        }
+    }
+
+    #[test]
+    fn keygen_is_deterministic() {
+        let bls = scheme();
+        let (sk, pk) = bls.keygen(b"key material").unwrap();
+        assert!(sk > 0 && sk < 13);
+        assert_eq!(bls.keygen(b"key material"), Ok((sk, pk)));
+    }
+
+    #[test]
+    fn aggregate_and_verify() {
+        let bls = scheme();
+        let keys: Vec<_> = [b"k1", b"k2", b"k3"]
+            .iter()
+            .map(|ikm| bls.keygen(*ikm).unwrap())
+            .collect();
+        let pks: Vec<_> = keys.iter().map(|(_, pk)| pk.clone()).collect();
+        let sigs: Vec<_> = keys
+            .iter()
+            .zip(MSGS)
+            .map(|((sk, _), msg)| bls.sign(sk, msg).unwrap())
+            .collect();
+        let sig = bls.aggregate(&sigs).unwrap();
+        assert!(bls.verify_aggregate(&pks, &MSGS, &sig).unwrap());
+
+        let swapped = [MSGS[1], MSGS[0], MSGS[2]];
+        assert!(!bls.verify_aggregate(&pks, &swapped, &sig).unwrap());
+        let partial = bls.aggregate(&sigs[..2]).unwrap();
+        assert!(!bls.verify_aggregate(&pks, &MSGS, &partial).unwrap());
+        let repeated = [MSGS[0], MSGS[0], MSGS[2]];
+        assert!(bls.verify_aggregate(&pks, &repeated, &sig).is_err());
+    }
+
+    #[test]
+    fn reject_identity_keys_and_signatures() {
+        let bls = scheme();
+        let (sk, pk) = bls.keygen(b"key material").unwrap();
+        let sig = bls.sign(&sk, MSGS[0]).unwrap();
+        let zero = ECPoint::PointAtInfinity;
+
+        // e(H(m), 0) = e(0, G2) = 1 for every message
+        assert!(!bls.verify(&zero, MSGS[0], &zero).unwrap());
+        assert!(!bls.verify(&zero, MSGS[0], &sig).unwrap());
+        assert!(!bls.verify(&pk, MSGS[0], &zero).unwrap());
+        assert!(!bls
+            .verify_aggregate(&[zero.clone(), zero.clone()], &MSGS[..2], &zero)
+            .unwrap());
+    }
+
+    #[test]
+    fn reject_off_curve_points() {
+        let bls = scheme();
+        let (sk, pk) = bls.keygen(b"key material").unwrap();
+        let sig = bls.sign(&sk, MSGS[0]).unwrap();
+
+        // 1 ≠ 0³ + 0
+        let off_curve = ECPoint::AffinePoint(K::zero(), K::one());
+
+        assert!(bls.verify(&pk, MSGS[0], &sig).unwrap());
+        assert!(!bls.verify(&pk, MSGS[0], &off_curve).unwrap());
+        assert!(!bls.verify(&off_curve, MSGS[0], &sig).unwrap());
+        assert!(!bls.verify_aggregate(&[pk], &MSGS[..1], &off_curve).unwrap());
+    }
+}
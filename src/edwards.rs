@@ -0,0 +1,280 @@
+use rug::{Complete, Integer};
+
+use crate::{
+    elliptic_curve::{ECPoint, EllipticCurve},
+    errors::ErrorKind,
+    field::Field,
+    montgomery::MontgomeryCurve,
+};
+
+// Twisted Edwards curve a x² + y² = 1 + d x² y²
+// The addition law is complete when a is a square and d is not: no special case is needed
+// for doubling, the neutral element or opposite points
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdwardsCurve<F: Field> {
+    a: F,
+    d: F,
+}
+
+// Point on a twisted Edwards curve (affine coords), the neutral element is (0, 1)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EdwardsPoint<F: Field>(pub F, pub F);
+
+impl<F: Field + Clone + PartialEq> EdwardsCurve<F> {
+    // New twisted Edwards curve, a and d must be distinct and non-zero
+    pub fn new(a: F, d: F) -> Result<Self, ErrorKind> {
+        if a == F::zero() || d == F::zero() || a == d {
+            return Err(ErrorKind::InvalidInput(
+                "Edwards coefficients must be distinct and non-zero",
+            ));
+        }
+        Ok(EdwardsCurve { a, d })
+    }
+
+    // Get (a, d)
+    pub fn get_coefficients(&self) -> (&F, &F) {
+        (&self.a, &self.d)
+    }
+
+    // Neutral element (0, 1)
+    pub fn identity() -> EdwardsPoint<F> {
+        EdwardsPoint(F::zero(), F::one())
+    }
+
+    // Check that point is on the curve
+    pub fn is_on_curve(&self, pt_p: &EdwardsPoint<F>) -> bool {
+        let EdwardsPoint(x, y) = pt_p;
+        let x2 = x.clone().square();
+        let y2 = y.clone().square();
+        // a x² + y² = 1 + d x² y²
+        self.a.clone().mul(&x2).add(&y2) == F::one().add(&self.d.clone().mul(&x2).mul(&y2))
+    }
+
+    // Returns the addition of P with Q
+    // /!\ Fails if a denominator vanishes, which only happens when the law is not complete
+    // (a not a square or d a square)
+    pub fn add(
+        &self,
+        pt_p: &EdwardsPoint<F>,
+        pt_q: &EdwardsPoint<F>,
+    ) -> Result<EdwardsPoint<F>, ErrorKind> {
+        let (EdwardsPoint(x_p, y_p), EdwardsPoint(x_q, y_q)) = (pt_p, pt_q);
+
+        // t = d xP xQ yP yQ
+        let x_pq = x_p.clone().mul(x_q);
+        let y_pq = y_p.clone().mul(y_q);
+        let t = self.d.clone().mul(&x_pq).mul(&y_pq);
+
+        let denom_x = F::one().add(&t);
+        let denom_y = F::one().add(&t.neg());
+        if denom_x == F::zero() || denom_y == F::zero() {
+            return Err(ErrorKind::InvalidInput(
+                "The addition law is not complete on this curve",
+            ));
+        }
+
+        // x = (xP yQ + yP xQ) / (1 + t)
+        // y = (yP yQ - a xP xQ) / (1 - t)
+        let x = x_p
+            .clone()
+            .mul(y_q)
+            .add(&y_p.clone().mul(x_q))
+            .div(&denom_x);
+        let y = y_pq.add(&self.a.clone().mul(&x_pq).neg()).div(&denom_y);
+        Ok(EdwardsPoint(x, y))
+    }
+
+    // Returns 2P, with the same formula as the addition
+    pub fn double(&self, pt_p: &EdwardsPoint<F>) -> Result<EdwardsPoint<F>, ErrorKind> {
+        self.add(pt_p, pt_p)
+    }
+
+    // Returns the inverse (-x, y) of P
+    pub fn neg(&self, pt_p: &EdwardsPoint<F>) -> EdwardsPoint<F> {
+        EdwardsPoint(pt_p.0.clone().neg(), pt_p.1.clone())
+    }
+
+    // Returns [k]P with double-and-add
+    // Negative scalars give -[|k|]P
+    pub fn mul(&self, k: &Integer, pt_p: &EdwardsPoint<F>) -> Result<EdwardsPoint<F>, ErrorKind> {
+        let k_abs = k.abs_ref().complete();
+        let mut res = EdwardsCurve::identity();
+        for i in (0..k_abs.significant_bits()).rev() {
+            res = self.double(&res)?;
+            if k_abs.get_bit(i) {
+                res = self.add(&res, pt_p)?;
+            }
+        }
+        if *k < 0 {
+            res = self.neg(&res);
+        }
+        Ok(res)
+    }
+
+    // Birationally equivalent Montgomery curve B v² = u³ + A u² + u,
+    // with A = 2 (a + d) / (a - d) and B = 4 / (a - d)
+    // Fails in characteristic 2, where B = 0
+    pub fn to_montgomery(&self) -> Result<MontgomeryCurve<F>, ErrorKind> {
+        let a_m_d = self.a.clone().add(&self.d.clone().neg());
+        MontgomeryCurve::new(
+            self.a.clone().add(&self.d).double().div(&a_m_d),
+            F::one().zmul(4).div(&a_m_d),
+        )
+    }
+
+    // Maps P to the curve returned by to_montgomery
+    // (x, y) -> (u, v) = ((1 + y) / (1 - y), u / x), the neutral element goes to the zero point
+    // and (0, -1) to the 2-torsion point (0, 0)
+    pub fn to_montgomery_point(&self, pt_p: &EdwardsPoint<F>) -> ECPoint<F> {
+        let EdwardsPoint(x, y) = pt_p;
+        if *x == F::zero() {
+            if *y == F::one() {
+                return ECPoint::PointAtInfinity;
+            }
+            // (0, -1)
+            return ECPoint::AffinePoint(F::zero(), F::zero());
+        }
+        let u = F::one().add(y).div(&F::one().add(&y.clone().neg()));
+        let v = u.clone().div(x);
+        ECPoint::AffinePoint(u, v)
+    }
+
+    // Maps a point of the curve returned by to_montgomery back to the Edwards curve
+    // (u, v) -> (x, y) = (u / v, (u - 1) / (u + 1))
+    // /!\ Fails on the points with v = 0 or u = -1 other than (0, 0), which correspond to points
+    // at infinity of the Edwards curve
+    pub fn from_montgomery_point(&self, pt_p: &ECPoint<F>) -> Result<EdwardsPoint<F>, ErrorKind> {
+        let (u, v) = match pt_p {
+            ECPoint::PointAtInfinity => return Ok(EdwardsCurve::identity()),
+            ECPoint::AffinePoint(u, v) => (u, v),
+        };
+
+        if *u == F::zero() && *v == F::zero() {
+            return Ok(EdwardsPoint(F::zero(), F::one().neg()));
+        }
+        let u_p_1 = u.clone().add(&F::one());
+        if *v == F::zero() || u_p_1 == F::zero() {
+            return Err(ErrorKind::InvalidInput(
+                "Point is at infinity on the Edwards curve",
+            ));
+        }
+        Ok(EdwardsPoint(
+            u.clone().div(v),
+            u.clone().add(&F::one().neg()).div(&u_p_1),
+        ))
+    }
+
+    // Birationally equivalent short Weierstrass curve, through the Montgomery form
+    // Fails in characteristic 2, see to_montgomery
+    // /!\ The characteristic must not be 3
+    pub fn to_weierstrass(&self) -> Result<EllipticCurve<F>, ErrorKind> {
+        Ok(self.to_montgomery()?.to_weierstrass())
+    }
+
+    // Maps P to the curve returned by to_weierstrass
+    pub fn to_weierstrass_point(&self, pt_p: &EdwardsPoint<F>) -> Result<ECPoint<F>, ErrorKind> {
+        Ok(self
+            .to_montgomery()?
+            .to_weierstrass_point(&self.to_montgomery_point(pt_p)))
+    }
+
+    // Maps a point of the curve returned by to_weierstrass back to the Edwards curve
+    // /!\ Fails on the points at infinity of the Edwards curve, see from_montgomery_point
+    pub fn from_weierstrass_point(&self, pt_p: &ECPoint<F>) -> Result<EdwardsPoint<F>, ErrorKind> {
+        self.from_montgomery_point(&self.to_montgomery()?.from_weierstrass_point(pt_p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fields::Fp;
+
+    type F = Fp<103>;
+
+    // x² + y² = 1 - x² y² over F103, complete since -1 is not a square mod 103
+    fn curve() -> EdwardsCurve<F> {
+        EdwardsCurve::new(F::one(), F::one().neg()).unwrap()
+    }
+
+    // Point with the first abscissa x from `seed` on such that y² = (1 - x²) / (1 + x²) is a square
+    fn point(seed: i64) -> EdwardsPoint<F> {
+        (seed..)
+            .find_map(|x| {
+                let pt = EdwardsPoint(F::new(x), F::zero());
+                let x2 = pt.0.clone().square();
+                let y = F::one()
+                    .add(&x2.clone().neg())
+                    .div(&F::one().add(&x2))
+                    .sqrt();
+                let pt = EdwardsPoint(pt.0, y);
+                curve().is_on_curve(&pt).then_some(pt)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn mul_negative() {
+        let curve = curve();
+        let pt = point(2);
+        for k in 1..20 {
+            let k = Integer::from(k);
+            let pt_k = curve.mul(&k, &pt).unwrap();
+            let pt_neg_k = curve.mul(&(-k), &pt).unwrap();
+            assert_eq!(pt_neg_k, curve.neg(&pt_k));
+            assert_eq!(
+                curve.add(&pt_k, &pt_neg_k).unwrap(),
+                EdwardsCurve::identity()
+            );
+        }
+    }
+
+    #[test]
+    fn weierstrass_round_trip() {
+        let curve = curve();
+        let weierstrass = curve.to_weierstrass().unwrap();
+        let pt = point(2);
+        let pt_w = curve.to_weierstrass_point(&pt).unwrap();
+        assert!(weierstrass.is_on_curve(&pt_w));
+        assert_eq!(curve.from_weierstrass_point(&pt_w).unwrap(), pt);
+    }
+
+    #[test]
+    fn group_law() {
+        let curve = curve();
+        let identity = EdwardsCurve::identity();
+        let (pt_p, pt_q, pt_r) = (point(2), point(5), point(9));
+        for pt in [&pt_p, &pt_q, &pt_r] {
+            assert_eq!(curve.add(pt, &identity).unwrap(), *pt);
+            assert_eq!(curve.add(&identity, pt).unwrap(), *pt);
+            assert_eq!(curve.add(pt, &curve.neg(pt)).unwrap(), identity);
+        }
+        assert!(curve.is_on_curve(&identity));
+
+        // (P + Q) + R = P + (Q + R), and P + Q = Q + P
+        let pq = curve.add(&pt_p, &pt_q).unwrap();
+        let qr = curve.add(&pt_q, &pt_r).unwrap();
+        assert!(curve.is_on_curve(&pq));
+        assert_eq!(pq, curve.add(&pt_q, &pt_p).unwrap());
+        assert_eq!(
+            curve.add(&pq, &pt_r).unwrap(),
+            curve.add(&pt_p, &qr).unwrap()
+        );
+        assert_eq!(
+            curve.double(&pt_p).unwrap(),
+            curve.add(&pt_p, &pt_p).unwrap()
+        );
+    }
+
+    #[test]
+    fn add_fails_on_incomplete_curve() {
+        // x² + y² = 1 + 4x² y², d = 2² is a square, 1 + d xP xQ yP yQ = 0 for the points below
+        let curve = EdwardsCurve::new(F::one(), F::new(4)).unwrap();
+        let pt_p = EdwardsPoint(F::new(4), F::new(8));
+        let pt_q = EdwardsPoint(F::new(13), F::new(45));
+        assert!(curve.is_on_curve(&pt_p) && curve.is_on_curve(&pt_q));
+        assert!(curve.add(&pt_p, &pt_q).is_err());
+        // Not every pair is exceptional
+        assert!(curve.add(&pt_p, &EdwardsCurve::identity()).is_ok());
+    }
+}
@@ -1,36 +1,324 @@
-use crate::{errors::ErrorKind, field::Field};
+use alloc::{vec, vec::Vec};
+use core::hash::{Hash, Hasher};
+use rug::{Complete, Integer};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{
+    de::{DeserializeSeed, Error as _},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::{
+    errors::ErrorKind,
+    field::{sgn0, Field, FieldBytes},
+    pairings::weil_pairing,
+    params::{parse_element, CurveParams},
+    poly::Polynomial,
+};
+
+#[cfg(feature = "hash")]
+use crate::hash::hash_to_field;
 
 type WCoeffs<F> = (F, F, F, F, F, F);
 
+// Basis ((a1, b1), (a2, b2)) of the GLV lattice {(a, b) : a + b λ = 0 mod n}
+pub type LatticeBasis = ((Integer, Integer), (Integer, Integer));
+
+// Line coefficients (c0, cx, cy), for the line c0 + cx x + cy y = 0
+pub type LineCoeffs<F> = (F, F, F);
+
+// Number of abscissas tried by random_point before giving up
+const RANDOM_POINT_ATTEMPTS: usize = 128;
+
+// Number of points tried by torsion_basis to complete the first basis point
+const TORSION_BASIS_ATTEMPTS: usize = 64;
+
+// Number of candidates ±1, ±2, .. tried by with_sswu_z for the constant Z
+const SSWU_Z_ATTEMPTS: i64 = 64;
+
 // Generic elliptic curve
-#[derive(Clone, Debug, PartialEq)]
+// Deserialization goes through EllipticCurve::new, so that the characteristic is checked
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct EllipticCurve<F: Field> {
     weierstrass_coefficients: WCoeffs<F>,
+
+    // Order r of the subgroup of interest and cofactor #E / r, when known
+    order: Option<Integer>,
+    cofactor: Option<Integer>,
+
+    // Constant Z of the simplified SWU map, when set by with_sswu_z
+    sswu_z: Option<F>,
+}
+
+// Curves are equal when their a-invariants are equal as field elements, whatever their
+// representation: the coefficients are compared with ct_eq, which reduces them, and the results
+// are combined with & so that all of them are compared whatever the first difference
+// The order, cofactor and SSWU constant are metadata on the curve and are not compared
+impl<F: Field> PartialEq for EllipticCurve<F> {
+    fn eq(&self, other: &Self) -> bool {
+        let (a1, a2, a3, a4, a5, a6) = &self.weierstrass_coefficients;
+        let (b1, b2, b3, b4, b5, b6) = &other.weierstrass_coefficients;
+        a1.ct_eq(b1) & a2.ct_eq(b2) & a3.ct_eq(b3) & a4.ct_eq(b4) & a5.ct_eq(b5) & a6.ct_eq(b6)
+    }
 }
 
 // Rational point on an elliptic curve (affine coords)
-#[derive(Clone, Debug, PartialEq)]
+// Eq and Hash are available when F has them, F must hash canonical representatives
+// Points are deserialized against a curve with EllipticCurve::point_seed, which checks them
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum ECPoint<F: Field + Clone> {
     AffinePoint(F, F),
     PointAtInfinity,
 }
 
+// Affine points are equal when their coords are equal as field elements, whatever their
+// representation: the coords are compared with ct_eq, which reduces them, and the results are
+// combined with & so that y is compared even when x differs
+impl<F: Field + Clone> PartialEq for ECPoint<F> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ECPoint::AffinePoint(x1, y1), ECPoint::AffinePoint(x2, y2)) => {
+                x1.ct_eq(x2) & y1.ct_eq(y2)
+            }
+            (ECPoint::PointAtInfinity, ECPoint::PointAtInfinity) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<F: Field + Clone + Eq> Eq for ECPoint<F> {}
+
+// Consistent with PartialEq only if equal field elements hash equally, i.e. F hashes the same
+// canonical representatives ct_eq compares
+impl<F: Field + Clone + Hash> Hash for ECPoint<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        if let ECPoint::AffinePoint(x, y) = self {
+            x.hash(state);
+            y.hash(state);
+        }
+    }
+}
+
+// Serialized forms of EllipticCurve and ECPoint, checked before being turned into them
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "EllipticCurve")]
+struct CurveData<F> {
+    weierstrass_coefficients: WCoeffs<F>,
+    order: Option<Integer>,
+    cofactor: Option<Integer>,
+    sswu_z: Option<F>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "ECPoint")]
+enum PointData<F> {
+    AffinePoint(F, F),
+    PointAtInfinity,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F> Deserialize<'de> for EllipticCurve<F>
+where
+    F: Field + Clone + PartialEq + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = CurveData::deserialize(deserializer)?;
+        let mut curve =
+            EllipticCurve::new(data.weierstrass_coefficients).map_err(D::Error::custom)?;
+        // The SSWU constant is searched again rather than trusted
+        if let Some(z) = data.sswu_z {
+            curve = curve.with_sswu_z().map_err(D::Error::custom)?;
+            if curve.sswu_z() != Some(&z) {
+                return Err(D::Error::custom("Invalid SSWU constant"));
+            }
+        }
+        Ok(EllipticCurve {
+            order: data.order,
+            cofactor: data.cofactor,
+            ..curve
+        })
+    }
+}
+
+// Deserializer of points on a given curve, which rejects the points that are not on it
+// See EllipticCurve::point_seed
+#[cfg(feature = "serde")]
+pub struct PointSeed<'a, F: Field>(&'a EllipticCurve<F>);
+
+#[cfg(feature = "serde")]
+impl<'de, F> DeserializeSeed<'de> for PointSeed<'_, F>
+where
+    F: Field + Clone + PartialEq + Deserialize<'de>,
+{
+    type Value = ECPoint<F>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<ECPoint<F>, D::Error> {
+        match PointData::deserialize(deserializer)? {
+            PointData::AffinePoint(x, y) => self.0.point(x, y).map_err(D::Error::custom),
+            PointData::PointAtInfinity => Ok(ECPoint::PointAtInfinity),
+        }
+    }
+}
+
+// Precomputed multiples of a fixed base point B, for scalars of at most `bits` bits
+// Window j holds [d 2^(w j)]B for d = 1 .. 2^w - 1, where w is the window size
+#[derive(Clone, Debug, PartialEq)]
+pub struct BaseTable<F: Field + Clone> {
+    window: usize,
+    bits: u32,
+    table: Vec<Vec<ECPoint<F>>>,
+}
+
 // Elliptic curve data structure
 impl<F: Field + Clone + PartialEq> EllipticCurve<F> {
     // New curve, long Weierstrass form
     // y² + a1 xy + a3 y = x³ + a2 x² + a4 x + a6
+    // Fails in characteristic 2 or 3: the group law, the line functions and solve_y divide by 2
+    // or 3, and are wrong there
+    pub fn new(coeffs: WCoeffs<F>) -> Result<Self, ErrorKind> {
+        if F::characteristic() <= 3 {
+            return Err(ErrorKind::InvalidInput(
+                "Curves in characteristic 2 or 3 are not supported",
+            ));
+        }
+        Ok(Self::new_long_weierstrass(coeffs))
+    }
+
+    // New curve, long Weierstrass form, unchecked
+    // /!\ The characteristic must not be 2 or 3, see new
     pub fn new_long_weierstrass(coeffs: WCoeffs<F>) -> Self {
         EllipticCurve {
             weierstrass_coefficients: coeffs,
+            order: None,
+            cofactor: None,
+            sswu_z: None,
+        }
+    }
+
+    // Sets the order r of the subgroup of interest
+    pub fn with_order(mut self, order: Integer) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    // Sets the cofactor #E / r
+    pub fn with_cofactor(mut self, cofactor: Integer) -> Self {
+        self.cofactor = Some(cofactor);
+        self
+    }
+
+    // Get the subgroup order, if known
+    pub fn order(&self) -> Option<&Integer> {
+        self.order.as_ref()
+    }
+
+    // Get the cofactor, if known
+    pub fn cofactor(&self) -> Option<&Integer> {
+        self.cofactor.as_ref()
+    }
+
+    // Get the constant Z of the simplified SWU map, if set
+    pub fn sswu_z(&self) -> Option<&F> {
+        self.sswu_z.as_ref()
+    }
+
+    // Whether [r]P = 0, with r the subgroup order set by with_order
+    // Fails if the order is not known
+    pub fn is_in_subgroup(&self, pt_p: &ECPoint<F>) -> Result<bool, ErrorKind> {
+        let order = self
+            .order
+            .as_ref()
+            .ok_or(ErrorKind::InvalidInput("The curve order is not known"))?;
+        Ok(self.mul(order, pt_p)? == ECPoint::PointAtInfinity)
+    }
+
+    // New curve from a descriptor, along with its generator, with its order and cofactor set
+    // Fails unless F has the given base prime p > 3, the curve is non-singular, and the generator
+    // is on the curve and killed by the group order
+    pub fn from_params(params: &CurveParams) -> Result<(Self, ECPoint<F>), ErrorKind> {
+        let p = params.base_prime()?;
+        if F::base_order() != p {
+            return Err(ErrorKind::InvalidInput(
+                "The base prime does not match the field",
+            ));
         }
+
+        let [a1, a2, a3, a4, a6] = &params.coefficients;
+        let curve = EllipticCurve::new((
+            parse_element(a1, &p)?,
+            parse_element(a2, &p)?,
+            parse_element(a3, &p)?,
+            parse_element(a4, &p)?,
+            F::zero(),
+            parse_element(a6, &p)?,
+        ))?;
+        if curve.discriminant() == F::zero() {
+            return Err(ErrorKind::InvalidInput("The curve is singular"));
+        }
+
+        let curve = curve
+            .with_order(params.group_order()?)
+            .with_cofactor(params.cofactor()?);
+
+        let (x, y) = &params.generator;
+        let generator = curve.point(parse_element(x, &p)?, parse_element(y, &p)?)?;
+        if !curve.is_in_subgroup(&generator)? {
+            return Err(ErrorKind::InvalidInput(
+                "The generator is not killed by the group order",
+            ));
+        }
+
+        Ok((curve, generator))
+    }
+
+    // Discriminant, the curve is singular when it is zero
+    // Δ = -b2² b8 - 8b4³ - 27b6² + 9b2 b4 b6, see b_invariants
+    pub fn discriminant(&self) -> F {
+        let (b2, b4, b6, b8) = self.b_invariants();
+        b2.clone()
+            .square()
+            .mul(&b8)
+            .neg()
+            .add(&b4.clone().zpow(3).zmul(-8))
+            .add(&b6.clone().square().zmul(-27))
+            .add(&b2.mul(&b4).mul(&b6).zmul(9))
+    }
+
+    // Returns (b2, b4, b6, b8) with b2 = a1² + 4a2, b4 = 2a4 + a1 a3, b6 = a3² + 4a6,
+    // b8 = a1² a6 + 4a2 a6 - a1 a3 a4 + a2 a3² - a4²
+    fn b_invariants(&self) -> (F, F, F, F) {
+        let (a1, a2, a3, a4, _, a6) = self.get_a_invariants();
+        let a1_2 = a1.clone().square();
+        let b2 = a1_2.clone().add(&a2.clone().zmul(4));
+        let b4 = a4.clone().double().add(&a1.clone().mul(a3));
+        let b6 = a3.clone().square().add(&a6.clone().zmul(4));
+        let b8 = a1_2
+            .mul(a6)
+            .add(&a2.clone().mul(a6).zmul(4))
+            .add(&a1.clone().mul(a3).mul(a4).neg())
+            .add(&a2.clone().mul(&a3.clone().square()))
+            .add(&a4.clone().square().neg());
+        (b2, b4, b6, b8)
     }
 
     // Check that point is on the curve
-    pub fn is_on_curve(self, p: &ECPoint<F>) -> bool {
+    pub fn is_on_curve(&self, p: &ECPoint<F>) -> bool {
         match p {
             ECPoint::PointAtInfinity => true,
             ECPoint::AffinePoint(x_p, y_p) => {
                 let (a1, a2, a3, a4, _, a6) = self.get_a_invariants();
+                if self.is_short_weierstrass() {
+                    // y² = x³ + a4 x + a6
+                    return y_p.clone().square() == x_p.clone().square().add(a4).mul(x_p).add(a6);
+                }
+
                 // y² + a1 xy + a3 y = x³ + a2 x² + a4 x + a6
                 y_p.clone()
                     .square()
@@ -46,31 +334,506 @@ impl<F: Field + Clone + PartialEq> EllipticCurve<F> {
         }
     }
 
+    // New point from affine coords, checking that it is on the curve
+    pub fn point(&self, x: F, y: F) -> Result<ECPoint<F>, ErrorKind> {
+        let pt = ECPoint::new_affine(x, y);
+        if !self.is_on_curve(&pt) {
+            return Err(ErrorKind::InvalidInput("Point is not on the curve"));
+        }
+        Ok(pt)
+    }
+
+    // Deserializer of points on this curve, e.g. curve.point_seed().deserialize(deserializer)
+    // Fails on points that are not on the curve, see point
+    #[cfg(feature = "serde")]
+    pub fn point_seed(&self) -> PointSeed<'_, F> {
+        PointSeed(self)
+    }
+
+    // Uncompressed encoding of P, with L = F::byte_len(): 0x04 || x || y for an affine point,
+    // and 0x00 followed by 2L zero bytes for the zero point, so that every point takes 1 + 2L bytes
+    pub fn to_uncompressed(&self, pt_p: &ECPoint<F>) -> Vec<u8>
+    where
+        F: FieldBytes,
+    {
+        let len = F::byte_len();
+        let mut bytes = Vec::with_capacity(1 + 2 * len);
+        match pt_p {
+            ECPoint::PointAtInfinity => bytes.resize(1 + 2 * len, 0),
+            ECPoint::AffinePoint(x, y) => {
+                bytes.push(0x04);
+                bytes.extend_from_slice(&x.to_bytes());
+                bytes.extend_from_slice(&y.to_bytes());
+            }
+        }
+        bytes
+    }
+
+    // Decodes a point encoded by to_uncompressed
+    // Fails on a wrong length or flag, a non-canonical coordinate, or a point not on the curve
+    pub fn from_uncompressed(&self, bytes: &[u8]) -> Result<ECPoint<F>, ErrorKind>
+    where
+        F: FieldBytes,
+    {
+        let len = F::byte_len();
+        if bytes.len() != 1 + 2 * len {
+            return Err(ErrorKind::InvalidInput("Invalid encoding length"));
+        }
+        match bytes[0] {
+            0x00 if bytes[1..].iter().all(|b| *b == 0) => Ok(ECPoint::PointAtInfinity),
+            0x04 => {
+                let x = F::from_bytes(&bytes[1..1 + len]);
+                let y = F::from_bytes(&bytes[1 + len..]);
+                match (x, y) {
+                    (Some(x), Some(y)) => self.point(x, y),
+                    _ => Err(ErrorKind::InvalidInput("Invalid coordinate encoding")),
+                }
+            }
+            _ => Err(ErrorKind::InvalidInput("Invalid point encoding")),
+        }
+    }
+
+    // Whether the curve is in short Weierstrass form, i.e. a1 = a2 = a3 = 0
+    fn is_short_weierstrass(&self) -> bool {
+        let (a1, a2, a3, _, _, _) = self.get_a_invariants();
+        *a1 == F::zero() && *a2 == F::zero() && *a3 == F::zero()
+    }
+
     // Random point
-    pub fn random_point(self) -> ECPoint<F> {
+    // Random abscissas are drawn until one of them is that of a point on the curve
+    // /!\ The point is not in any particular subgroup, see random_point_in_subgroup
+    pub fn random_point(&self) -> Result<ECPoint<F>, ErrorKind> {
+        for _ in 0..RANDOM_POINT_ATTEMPTS {
+            let rand_x = F::random_element();
+            if let Some(rand_y) = self.solve_y(&rand_x) {
+                return Ok(ECPoint::AffinePoint(rand_x, rand_y));
+            }
+        }
+        Err(ErrorKind::InvalidInput("No point found on the curve"))
+    }
+
+    // Cofactor clearing, [cofactor]P
+    // Sends any point of the curve to the subgroup of order r when cofactor = #E / r
+    pub fn mul_by_cofactor(
+        &self,
+        pt_p: &ECPoint<F>,
+        cofactor: &Integer,
+    ) -> Result<ECPoint<F>, ErrorKind> {
+        self.mul(cofactor, pt_p)
+    }
+
+    // Random non-zero point of the subgroup of order r, where cofactor = #E / r
+    // Random points are multiplied by the cofactor until the result is not the zero point
+    pub fn random_point_in_subgroup(&self, cofactor: &Integer) -> Result<ECPoint<F>, ErrorKind> {
+        for _ in 0..RANDOM_POINT_ATTEMPTS {
+            let pt = self.mul_by_cofactor(&self.random_point()?, cofactor)?;
+            if pt != ECPoint::PointAtInfinity {
+                return Ok(pt);
+            }
+        }
+        Err(ErrorKind::InvalidInput("No point found in the subgroup"))
+    }
+
+    // Basis (P, Q) of the r-torsion E[r] ≅ Z/r × Z/r, where full_group_order is the number of
+    // points of the curve over F
+    // Random points are sent to E[r] by multiplying them by the part of full_group_order prime
+    // to r, then by r until they are of order r. P and Q are independent when their Weil pairing
+    // is not one, i.e. is a primitive r-th root of unity
+    // Returns None if no basis is found after a bounded number of attempts, for instance when
+    // E[r] is not defined over F
+    // /!\ r must be prime
+    pub fn torsion_basis(
+        &self,
+        r: &Integer,
+        full_group_order: &Integer,
+    ) -> Option<(ECPoint<F>, ECPoint<F>)> {
+        if *r <= 1 {
+            return None;
+        }
+        let mut cofactor = full_group_order.clone();
+        let mut valuation = 0u32;
+        while cofactor.is_divisible(r) && cofactor != 0 {
+            cofactor /= r;
+            valuation += 1;
+        }
+
+        let random_torsion_point = || -> Option<ECPoint<F>> {
+            for _ in 0..RANDOM_POINT_ATTEMPTS {
+                let mut pt = self.mul(&cofactor, &self.random_point().ok()?).ok()?;
+                if pt == ECPoint::PointAtInfinity {
+                    continue;
+                }
+                // [cofactor]pt has order dividing r^valuation when full_group_order is right,
+                // so more multiplications mean the order given was wrong
+                for _ in 0..=valuation {
+                    let pt_r = self.mul(r, &pt).ok()?;
+                    if pt_r == ECPoint::PointAtInfinity {
+                        return Some(pt);
+                    }
+                    pt = pt_r;
+                }
+                return None;
+            }
+            None
+        };
+
+        let pt_p = random_torsion_point()?;
+        for _ in 0..TORSION_BASIS_ATTEMPTS {
+            let pt_q = random_torsion_point()?;
+            let e = weil_pairing(self, pt_p.clone(), pt_q.clone(), r.clone()).ok()?;
+            if !e.is_one() {
+                return Some((pt_p, pt_q));
+            }
+        }
+        None
+    }
+
+    // Hash a message to a point of the subgroup, using try-and-increment
+    // x = hash_to_field(msg || ctr, dst), and ctr is incremented until x is the abscissa of a
+    // point whose cofactor multiple is not the zero point. As with hash_to_field, x is uniform
+    // in the prime subfield of F
+    // The cofactor is the one set by with_cofactor, so that the clearing step cannot be left
+    // out. Without it, the point is only on the curve
+    // Fails if no point is found after RANDOM_POINT_ATTEMPTS counters
+    // /!\ Not constant time: the number of attempts depends on the message
+    #[cfg(feature = "hash")]
+    pub fn hash_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<ECPoint<F>, ErrorKind> {
+        for ctr in 0..RANDOM_POINT_ATTEMPTS as u32 {
+            let data = [msg, &ctr.to_be_bytes()].concat();
+            let x = hash_to_field::<F>(&data, dst, 1)?.remove(0);
+
+            if let Some(y) = self.solve_y(&x) {
+                let pt = ECPoint::AffinePoint(x, y);
+                let pt = match &self.cofactor {
+                    Some(cofactor) => self.mul_by_cofactor(&pt, cofactor)?,
+                    None => pt,
+                };
+                if pt != ECPoint::PointAtInfinity {
+                    return Ok(pt);
+                }
+            }
+        }
+        Err(ErrorKind::InvalidInput("No point found on the curve"))
+    }
+
+    // Hash a message to a point of the subgroup, without rejection sampling
+    // Two field elements u0, u1 are derived with hash_to_field, and the result is
+    // [cofactor](sswu_map(u0) + sswu_map(u1)), as the _RO_ suites of RFC 9380
+    // The cofactor is the one set by with_cofactor, as in hash_to_curve. Without it, the point is
+    // only on the curve
+    // /!\ Same curve conditions as sswu_map. There is no rejection sampling, but neither the
+    // square roots nor the point addition are constant time
+    #[cfg(feature = "hash")]
+    pub fn hash_to_curve_sswu(&self, msg: &[u8], dst: &[u8]) -> Result<ECPoint<F>, ErrorKind>
+    where
+        F: FieldBytes,
+    {
+        let u: Vec<F> = hash_to_field(msg, dst, 2)?;
+        let pt_q0 = self.sswu_map(&u[0])?;
+        let pt_q1 = self.sswu_map(&u[1])?;
+        let pt = self.add(&pt_q0, &pt_q1)?;
+        match &self.cofactor {
+            Some(cofactor) => self.mul_by_cofactor(&pt, cofactor),
+            None => Ok(pt),
+        }
+    }
+
+    // Sets the constant Z of the simplified SWU map, needed by sswu_map
+    // Z is the first of 1, -1, 2, -2, .. that is a non-square other than -1 such that g(x) - Z is
+    // irreducible and g(B / ZA) is a square, g(x) = x³ + A x + B, as in RFC 9380 appendix H.2
+    // Fails if the curve does not meet the conditions of sswu_map, or no such Z is found
+    pub fn with_sswu_z(mut self) -> Result<Self, ErrorKind> {
+        let (a, b) = self.sswu_coefficients()?;
+        let is_square = |x: &F| x.clone().sqrt().square() == *x;
+        let g_b_za = |z: &F| {
+            let x = b.clone().div(&z.clone().mul(a));
+            x.clone().square().add(a).mul(&x).add(b)
+        };
+        let g = Polynomial::new(vec![b.clone(), a.clone(), F::zero(), F::one()]);
+
+        for z in (1..=SSWU_Z_ATTEMPTS).flat_map(|i| [F::one().zmul(i), F::one().zmul(-i)]) {
+            if z == F::one().neg() || is_square(&z) || !is_square(&g_b_za(&z)) {
+                continue;
+            }
+            // A cubic is irreducible iff it has no root
+            if !has_root(&g.sub(&Polynomial::constant(z.clone())))? {
+                self.sswu_z = Some(z);
+                return Ok(self);
+            }
+        }
+        Err(ErrorKind::InvalidInput("No suitable Z found for SSWU"))
+    }
+
+    // (A, B) for the curve y² = x³ + A x + B, which must be short Weierstrass with A, B ≠ 0
+    fn sswu_coefficients(&self) -> Result<(&F, &F), ErrorKind> {
+        let (_, _, _, a, _, b) = self.get_a_invariants();
+        if !self.is_short_weierstrass() || *a == F::zero() || *b == F::zero() {
+            return Err(ErrorKind::InvalidInput(
+                "SSWU needs a short Weierstrass curve with A, B ≠ 0",
+            ));
+        }
+        Ok((a, b))
+    }
+
+    // Simplified Shallue-van de Woestijne-Ulas map of u to the curve (RFC 9380, section 6.6.2)
+    // The curve must be y² = g(x) = x³ + A x + B with A, B ≠ 0, with its constant Z set by
+    // with_sswu_z. The map has no rejection sampling and always costs two square roots, but it
+    // branches on u and its timing depends on the field: it is not constant time
+    // The sign of y is that of u, as given by sgn0
+    pub fn sswu_map(&self, u: &F) -> Result<ECPoint<F>, ErrorKind>
+    where
+        F: FieldBytes,
+    {
+        let (a, b) = self.sswu_coefficients()?;
+        let z = self.sswu_z.as_ref().ok_or(ErrorKind::InvalidInput(
+            "The SSWU constant is not set, see with_sswu_z",
+        ))?;
+        let g = |x: &F| x.clone().square().add(a).mul(x).add(b);
+        let is_square = |x: &F| x.clone().sqrt().square() == *x;
+
+        // x1 = (-B / A) (1 + 1 / (Z² u⁴ + Z u²)), or B / ZA when the denominator vanishes
+        let z_u2 = z.clone().mul(&u.clone().square());
+        let tv1 = z_u2.clone().square().add(&z_u2);
+        let x1 = if tv1 == F::zero() {
+            b.clone().div(&z.clone().mul(a))
+        } else {
+            b.clone().div(a).neg().mul(&F::one().add(&tv1.invert()))
+        };
+
+        // Either g(x1) is a square, or g(x2) = Z³ u⁶ g(x1) is, with x2 = Z u² x1
+        let gx1 = g(&x1);
+        let (x, gx) = if is_square(&gx1) {
+            (x1, gx1)
+        } else {
+            let x2 = z_u2.mul(&x1);
+            let gx2 = g(&x2);
+            (x2, gx2)
+        };
+
+        let y = gx.clone().sqrt();
+        if y.clone().square() != gx {
+            return Err(ErrorKind::NonResidue);
+        }
+        let y = if sgn0(u) == sgn0(&y) { y } else { y.neg() };
+        Ok(ECPoint::AffinePoint(x, y))
+    }
+
+    // Returns (b, delta) where b = a1 x + a3 and delta is the discriminant of y² + b y + c = 0,
+    // the equation in y of the points of abscissa x
+    fn y_discriminant(&self, x: &F) -> (F, F) {
         let (a1, a2, a3, a4, _, a6) = self.get_a_invariants();
-        // Get a random x
-        let rand_x = F::random_element();
 
-        // y² + ( a1 x + a3 ) * y = x³ + a2 x² + a4 x + a6
         // b = a1 x + a3
-        let b = &rand_x.clone().mul(a1).add(a3);
+        let b = x.clone().mul(a1).add(a3);
 
         // c = - ( x³ + a2 x² + a4 x + a6 )
-        let c = &rand_x
+        let c = x
             .clone()
             .zpow(3)
-            .add(&rand_x.clone().square().mul(a2))
-            .add(&rand_x.clone().mul(a4))
+            .add(&x.clone().square().mul(a2))
+            .add(&x.clone().mul(a4))
             .add(a6)
             .neg();
-        let half = F::one().zmul(2).invert();
-        let delta = &b.clone().square().add(&c.clone().zmul(4).neg());
+        let delta = b.clone().square().add(&c.zmul(4).neg());
+        (b, delta)
+    }
+
+    // Returns some y such that (x, y) is on the curve, if there is one
+    fn solve_y(&self, x: &F) -> Option<F> {
+        let (b, delta) = self.y_discriminant(x);
+        let root = delta.clone().sqrt();
+        if root.clone().square() != delta {
+            return None;
+        }
 
         // y = ( - b + sqrt( delta ) ) / 2
-        let rand_y = half.mul(&b.clone().neg().add(&delta.clone().sqrt()));
+        let half = F::one().double().invert();
+        Some(half.mul(&b.neg().add(&root)))
+    }
+
+    // Number of points on the curve, including the zero point
+    // Naive enumeration of the abscissas, in O(p): only meant for small fields
+    // Fails unless F is a prime field, the abscissas of an extension field are not enumerated
+    // /!\ F must be of odd characteristic p
+    pub fn count_points(&self) -> Result<Integer, ErrorKind> {
+        if F::degree() != 1 {
+            return Err(ErrorKind::InvalidInput(
+                "Points can only be counted over a prime field",
+            ));
+        }
+        let p = F::characteristic();
+        let euler = (&p - 1u32).complete() >> 1;
+
+        // Zero point
+        let mut count = Integer::from(1);
+        let mut x = F::zero();
+        let mut i = Integer::new();
+        while i < p {
+            // Two points if delta is a non-zero square, one if delta = 0
+            let (_, delta) = self.y_discriminant(&x);
+            if delta == F::zero() {
+                count += 1;
+            } else if delta.pow(&euler).is_one() {
+                count += 2;
+            }
+            x = x.add(&F::one());
+            i += 1;
+        }
+        Ok(count)
+    }
+
+    // Trace of the Frobenius t = q + 1 - #E(Fq), from count_points
+    // /!\ Same restrictions as count_points, for large fields see check_trace
+    pub fn trace_of_frobenius(&self) -> Result<Integer, ErrorKind> {
+        Ok(F::characteristic() + 1u32 - self.count_points()?)
+    }
+
+    // Characteristic polynomial X² - tX + q of the Frobenius, returned as (t, q)
+    // /!\ Same restrictions as trace_of_frobenius
+    pub fn frobenius_charpoly(&self) -> Result<(Integer, Integer), ErrorKind> {
+        Ok((self.trace_of_frobenius()?, F::characteristic()))
+    }
+
+    // Eigenvalues (1, q mod r) of the Frobenius on E[r], those of the eigenspaces G1 and G2
+    // They are the roots of X² - tX + q mod r when r divides #E = q + 1 - t
+    // Fails if r <= 1
+    pub fn frobenius_eigenvalues_mod(&self, r: &Integer) -> Result<(Integer, Integer), ErrorKind> {
+        if *r <= 1 {
+            return Err(ErrorKind::InvalidInput("r must be greater than 1"));
+        }
+        Ok((Integer::from(1), F::characteristic().modulo(r)))
+    }
+
+    // Checks that t is a possible trace of the q-power Frobenius, with q the base field order,
+    // i.e. that it satisfies Hasse's bound t² <= 4q
+    pub fn check_trace(&self, trace: &Integer) -> Result<(), ErrorKind> {
+        if trace.square_ref().complete() > F::base_order() * 4u32 {
+            return Err(ErrorKind::InvalidInput("The trace exceeds Hasse's bound"));
+        }
+        Ok(())
+    }
+
+    // Whether the curve is supersingular, i.e. t = 0 mod p with t the trace of the Frobenius
+    // For p > 3 Hasse's bound leaves t = 0 as the only possibility
+    // /!\ Same restrictions as trace_of_frobenius: F must be a prime field of characteristic p > 3,
+    // the test is not valid in characteristic 2 or 3
+    pub fn is_supersingular(&self) -> Result<bool, ErrorKind> {
+        Ok(self
+            .trace_of_frobenius()?
+            .is_divisible(&F::characteristic()))
+    }
+
+    // Iterator over all the points of the curve, the zero point first then by increasing abscissa
+    // The ordinates of each abscissa are found with solve_y and neg_point, in O(p) overall
+    // /!\ F must be a prime field of odd characteristic p
+    pub fn points(&self) -> impl Iterator<Item = ECPoint<F>> + '_ {
+        let affine = prime_field_elements::<F>().flat_map(move |x| self.points_at(x));
+        core::iter::once(ECPoint::PointAtInfinity).chain(affine)
+    }
+
+    // Division polynomial ψn, as a polynomial in x
+    // ψn has a factor ψ2 = 2y + a1 x + a3 when n is even: the result is then ψn / ψ2. With
+    // ψ2² = 4x³ + b2 x² + 2b4 x + b6, the recurrence
+    // ψ(2m+1) = ψ(m+2) ψm³ - ψ(m-1) ψ(m+1)³, ψ(2m) = ψm (ψ(m+2) ψ(m-1)² - ψ(m-2) ψ(m+1)²) / ψ2
+    // is applied to these polynomials, multiplying by (ψ2²)² the term with two even indices
+    // An affine point P is n-torsion if and only if ψn(P) = 0
+    pub fn division_polynomial(&self, n: u32) -> Polynomial<F> {
+        let (b2, b4, b6, b8) = self.b_invariants();
+        let psi_2_sq = Polynomial::new(vec![
+            b6.clone(),
+            b4.clone().double(),
+            b2.clone(),
+            F::one().zmul(4),
+        ]);
+        let psi_2_sq_sq = psi_2_sq.square();
 
-        ECPoint::AffinePoint(rand_x, rand_y)
+        let mut psi = vec![
+            Polynomial::zero(),
+            Polynomial::constant(F::one()),
+            Polynomial::constant(F::one()),
+            Polynomial::new(vec![
+                b8.clone(),
+                b6.clone().zmul(3),
+                b4.clone().zmul(3),
+                b2.clone(),
+                F::one().zmul(3),
+            ]),
+            // ψ4 / ψ2 = 2x⁶ + b2 x⁵ + 5b4 x⁴ + 10b6 x³ + 10b8 x² + (b2 b8 - b4 b6) x + b4 b8 - b6²
+            Polynomial::new(vec![
+                b4.clone().mul(&b8).add(&b6.clone().square().neg()),
+                b2.clone().mul(&b8).add(&b4.clone().mul(&b6).neg()),
+                b8.zmul(10),
+                b6.zmul(10),
+                b4.zmul(5),
+                b2,
+                F::one().double(),
+            ]),
+        ];
+
+        let n = n as usize;
+        for k in psi.len()..=n {
+            let m = k / 2;
+            let next = if k % 2 == 1 {
+                let lhs = psi[m + 2].mul(&psi[m].square().mul(&psi[m]));
+                let rhs = psi[m - 1].mul(&psi[m + 1].square().mul(&psi[m + 1]));
+                if m % 2 == 0 {
+                    lhs.mul(&psi_2_sq_sq).sub(&rhs)
+                } else {
+                    lhs.sub(&rhs.mul(&psi_2_sq_sq))
+                }
+            } else {
+                let lhs = psi[m + 2].mul(&psi[m - 1].square());
+                let rhs = psi[m - 2].mul(&psi[m + 1].square());
+                psi[m].mul(&lhs.sub(&rhs))
+            };
+            psi.push(next);
+        }
+        psi.swap_remove(n)
+    }
+
+    // Points of the 2-torsion E[2], the zero point first
+    // Their abscissas are the roots of ψ2² = 4x³ + b2 x² + 2b4 x + b6, where the tangent is
+    // vertical, found by enumeration in O(p)
+    // /!\ Same restrictions as points
+    pub fn two_torsion(&self) -> Vec<ECPoint<F>> {
+        let mut torsion = vec![ECPoint::PointAtInfinity];
+        torsion.extend(
+            prime_field_elements::<F>()
+                .filter(|x| self.y_discriminant(x).1 == F::zero())
+                .flat_map(|x| self.points_at(x)),
+        );
+        torsion
+    }
+
+    // Points of the 3-torsion E[3], the zero point first
+    // Their abscissas are the roots of ψ3 = 3x⁴ + b2 x³ + 3b4 x² + 3b6 x + b8, where the tangent
+    // is an inflection tangent, found by enumeration in O(p)
+    // /!\ Same restrictions as points
+    pub fn three_torsion(&self) -> Vec<ECPoint<F>> {
+        let psi_3 = self.division_polynomial(3);
+        let mut torsion = vec![ECPoint::PointAtInfinity];
+        torsion.extend(
+            prime_field_elements::<F>()
+                .filter(|x| psi_3.evaluate(x) == F::zero())
+                .flat_map(|x| self.points_at(x)),
+        );
+        torsion
+    }
+
+    // Points of abscissa x, none, one or a point and its opposite
+    fn points_at(&self, x: F) -> Vec<ECPoint<F>> {
+        match self.solve_y(&x) {
+            None => vec![],
+            Some(y) => {
+                let pt = ECPoint::AffinePoint(x, y);
+                let pt_neg = self.neg_point(&pt);
+                if pt_neg == pt {
+                    vec![pt]
+                } else {
+                    vec![pt, pt_neg]
+                }
+            }
+        }
     }
 
     pub fn infinity_point() -> ECPoint<F> {
@@ -82,6 +845,111 @@ impl<F: Field + Clone + PartialEq> EllipticCurve<F> {
         &self.weierstrass_coefficients
     }
 
+    // Same curve over an extension G of F, with its coefficients mapped by `embed`
+    // The subgroup of order r is kept, but the cofactor is not, as E(G) has more points
+    pub fn base_change<G: Field + Clone + PartialEq>(
+        &self,
+        embed: impl Fn(&F) -> G,
+    ) -> EllipticCurve<G> {
+        let (a1, a2, a3, a4, a5, a6) = self.get_a_invariants();
+        EllipticCurve {
+            weierstrass_coefficients: (
+                embed(a1),
+                embed(a2),
+                embed(a3),
+                embed(a4),
+                embed(a5),
+                embed(a6),
+            ),
+            order: self.order.clone(),
+            cofactor: None,
+            sswu_z: None,
+        }
+    }
+
+    // Admissible change of variables (x, y) = (u² x' + r, u³ y' + s u² x' + t)
+    // Returns the curve in the (x', y') coordinates and the map sending points of this curve to it
+    // In characteristic not 2 or 3, s = -a1 / 2, r = -(a1² + 4 a2) / 12 and t = -(a3 + r a1) / 2
+    // give a short Weierstrass model
+    // The curves are isomorphic, so the order and cofactor are kept
+    #[allow(clippy::type_complexity)]
+    pub fn transform(
+        &self,
+        u: &F,
+        r: &F,
+        s: &F,
+        t: &F,
+    ) -> Result<(EllipticCurve<F>, impl Fn(&ECPoint<F>) -> ECPoint<F>), ErrorKind> {
+        if *u == F::zero() {
+            return Err(ErrorKind::InvalidInput("u must not be zero"));
+        }
+        let (a1, a2, a3, a4, _, a6) = self.get_a_invariants();
+        let u_inv = u.clone().invert();
+        let u2_inv = u_inv.clone().square();
+        let u3_inv = u2_inv.clone().mul(&u_inv);
+        let rs = r.clone().mul(s);
+
+        // u a1' = a1 + 2s
+        let b1 = a1.clone().add(&s.clone().double()).mul(&u_inv);
+        // u² a2' = a2 - s a1 + 3r - s²
+        let b2 = a2
+            .clone()
+            .add(&s.clone().mul(a1).neg())
+            .add(&r.clone().zmul(3))
+            .add(&s.clone().square().neg())
+            .mul(&u2_inv);
+        // u³ a3' = a3 + r a1 + 2t
+        let b3 = a3
+            .clone()
+            .add(&r.clone().mul(a1))
+            .add(&t.clone().double())
+            .mul(&u3_inv);
+        // u⁴ a4' = a4 - s a3 + 2r a2 - (t + rs) a1 + 3r² - 2st
+        let b4 = a4
+            .clone()
+            .add(&s.clone().mul(a3).neg())
+            .add(&r.clone().mul(a2).double())
+            .add(&t.clone().add(&rs).mul(a1).neg())
+            .add(&r.clone().square().zmul(3))
+            .add(&s.clone().mul(t).double().neg())
+            .mul(&u2_inv.clone().square());
+        // u⁶ a6' = a6 + r a4 + r² a2 + r³ - t a3 - t² - rt a1
+        let b6 = a6
+            .clone()
+            .add(&r.clone().mul(a4))
+            .add(&r.clone().square().mul(a2))
+            .add(&r.clone().zpow(3))
+            .add(&t.clone().mul(a3).neg())
+            .add(&t.clone().square().neg())
+            .add(&r.clone().mul(t).mul(a1).neg())
+            .mul(&u3_inv.clone().square());
+
+        // x' = (x - r) / u², y' = (y - s (x - r) - t) / u³
+        let (r, s, t) = (r.clone(), s.clone(), t.clone());
+        let map = move |pt: &ECPoint<F>| match pt {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(x, y) => {
+                let x_m_r = x.clone().add(&r.clone().neg());
+                let new_y = y
+                    .clone()
+                    .add(&s.clone().mul(&x_m_r).neg())
+                    .add(&t.clone().neg())
+                    .mul(&u3_inv);
+                ECPoint::AffinePoint(x_m_r.mul(&u2_inv), new_y)
+            }
+        };
+
+        Ok((
+            EllipticCurve {
+                weierstrass_coefficients: (b1, b2, b3, b4, F::zero(), b6),
+                order: self.order.clone(),
+                cofactor: self.cofactor.clone(),
+                sswu_z: None,
+            },
+            map,
+        ))
+    }
+
     // Returns the evaluation of the line PQ at R, where P is self
     // /!\ R cannot be the zero point
     pub fn line(
@@ -90,49 +958,112 @@ impl<F: Field + Clone + PartialEq> EllipticCurve<F> {
         pt_q: &ECPoint<F>,
         pt_r: &ECPoint<F>,
     ) -> Result<F, ErrorKind> {
-        let (x_r, y_r) = match pt_r {
-            // Case P = Q = 0
-            ECPoint::PointAtInfinity => {
-                return Err(ErrorKind::InvalidInput("R cannot be the point at infinity"))
+        let (x_r, y_r) = pt_r
+            .xy()
+            .ok_or(ErrorKind::InvalidInput("R cannot be the point at infinity"))?;
+
+        let (c0, cx, cy) = self.line_coefficients(pt_p, pt_q);
+        Ok(c0.add(&cx.mul(x_r)).add(&cy.mul(y_r)))
+    }
+
+    // Returns (P + Q, c) where c are the coefficients of the line PQ, see line_coefficients
+    // The slope of the line is computed once and used for both
+    pub fn add_with_line_coefficients(
+        &self,
+        pt_p: &ECPoint<F>,
+        pt_q: &ECPoint<F>,
+    ) -> Result<(ECPoint<F>, LineCoeffs<F>), ErrorKind> {
+        let coeffs = self.line_coefficients(pt_p, pt_q);
+        let (x_p, y_p, x_q) = match (pt_p, pt_q) {
+            (ECPoint::AffinePoint(x_p, y_p), ECPoint::AffinePoint(x_q, _))
+                if coeffs.2 != F::zero() =>
+            {
+                (x_p, y_p, x_q)
             }
-            ECPoint::AffinePoint(x, y) => (x, y),
+            // Vertical line: P or Q = 0, or P + Q = 0
+            _ => return Ok((self.add(pt_p, pt_q)?, coeffs)),
         };
 
+        // The line is y = slope x + nu, with slope = -cx
+        let (a1, a2, a3, _, _, _) = self.get_a_invariants();
+        let slope = coeffs.1.clone().neg();
+        let nu = y_p.clone().add(&slope.clone().mul(x_p).neg());
+
+        // x = slope² + a1 slope - a2 - xP - xQ
+        // y = -(slope + a1) x - nu - a3
+        let x = slope
+            .clone()
+            .square()
+            .add(&a1.clone().mul(&slope))
+            .add(&a2.clone().neg())
+            .add(&x_p.clone().neg())
+            .add(&x_q.clone().neg());
+        let y = slope.add(a1).mul(&x).add(&nu).add(a3).neg();
+        Ok((ECPoint::AffinePoint(x, y), coeffs))
+    }
+
+    // Returns (P + Q, l(R)) where l is the line PQ
+    // /!\ R cannot be the zero point
+    pub fn add_with_line(
+        &self,
+        pt_p: &ECPoint<F>,
+        pt_q: &ECPoint<F>,
+        pt_r: &ECPoint<F>,
+    ) -> Result<(ECPoint<F>, F), ErrorKind> {
+        let (x_r, y_r) = pt_r
+            .xy()
+            .ok_or(ErrorKind::InvalidInput("R cannot be the point at infinity"))?;
+
+        let (pt_s, (c0, cx, cy)) = self.add_with_line_coefficients(pt_p, pt_q)?;
+        Ok((pt_s, c0.add(&cx.mul(x_r)).add(&cy.mul(y_r))))
+    }
+
+    // Returns (2P, l(R)) where l is the tangent at P
+    // /!\ R cannot be the zero point
+    pub fn double_with_line(
+        &self,
+        pt_p: &ECPoint<F>,
+        pt_r: &ECPoint<F>,
+    ) -> Result<(ECPoint<F>, F), ErrorKind> {
+        self.add_with_line(pt_p, pt_p, pt_r)
+    }
+
+    // Returns the coefficients (c0, cx, cy) of the line PQ,
+    // whose evaluation at R = (xR, yR) is c0 + cx xR + cy yR
+    // The line is vertical (xR - xP) whenever P + Q = 0: for Q = -P, for P or Q = 0, and for the
+    // tangent at a 2-torsion point P, where 2y + a1 x + a3 = 0. No division by zero can occur
+    pub fn line_coefficients(&self, pt_p: &ECPoint<F>, pt_q: &ECPoint<F>) -> LineCoeffs<F> {
         match (pt_p, pt_q) {
             // Case P = Q = 0
-            (ECPoint::PointAtInfinity, ECPoint::PointAtInfinity) => Ok(F::one()),
+            (ECPoint::PointAtInfinity, ECPoint::PointAtInfinity) => {
+                (F::one(), F::zero(), F::zero())
+            }
             (ECPoint::PointAtInfinity, ECPoint::AffinePoint(x_q, _)) => {
                 // Case P = 0
                 // xR - xQ
-                let x_q_neg = x_q.clone().neg();
-                Ok(x_r.clone().add(&x_q_neg))
+                (x_q.clone().neg(), F::one(), F::zero())
             }
             (ECPoint::AffinePoint(x_p, _), ECPoint::PointAtInfinity) => {
                 // Case Q = 0
                 // xR - xP
-                let x_p_neg = x_p.clone().neg();
-                Ok(x_r.clone().add(&x_p_neg))
+                (x_p.clone().neg(), F::one(), F::zero())
             }
             (ECPoint::AffinePoint(x_p, y_p), ECPoint::AffinePoint(x_q, y_q)) => {
                 let x_p_neg = x_p.clone().neg();
                 let y_p_neg = y_p.clone().neg();
 
-                if (x_p != x_q) || (y_p != y_q) {
+                let slope = if (x_p != x_q) || (y_p != y_q) {
                     // Case P != Q
                     if x_p == x_q {
-                        // Case xP = xQ
+                        // Case xP = xQ, i.e. Q = -P
                         // xR - xP
-                        Ok(x_r.clone().add(&x_p_neg))
-                    } else {
-                        // Case xP != xQ
-                        let num = x_p_neg.clone().add(y_q);
-                        let denom = x_p_neg.clone().add(x_q);
-                        let slope = num.div(&denom);
-
-                        let xdiff = (x_r.clone().add(&x_p_neg)).mul(&slope).neg();
-                        let ydiff = y_r.clone().add(&y_p_neg);
-                        Ok(xdiff.add(&ydiff))
+                        return (x_p_neg, F::one(), F::zero());
                     }
+
+                    // Case xP != xQ
+                    let num = y_p_neg.clone().add(y_q);
+                    let denom = x_p_neg.add(x_q);
+                    num.div_unchecked(&denom)
                 } else {
                     // Case P = Q
                     let (a1, a2, a3, a4, _, _) = self.get_a_invariants();
@@ -144,76 +1075,72 @@ impl<F: Field + Clone + PartialEq> EllipticCurve<F> {
                         .zmul(3)
                         .add(&y_p.clone().mul(a1).neg())
                         .add(a4)
-                        .add(&x_p.clone().mul(a2).zmul(2));
+                        .add(&x_p.clone().mul(a2).double());
 
                     // 2y + x a1 + a3
-                    let denom = y_p.clone().zmul(2).add(a3).add(&x_p.clone().mul(a1));
+                    let denom = y_p.clone().double().add(a3).add(&x_p.clone().mul(a1));
 
                     if denom == F::zero() {
+                        // P is a 2-torsion point, the tangent is vertical
                         // xR - xP
-                        Ok(x_r.clone().add(&x_p_neg))
-                    } else {
-                        let slope = num.div(&denom);
-
-                        let xdiff = (x_r.clone().add(&x_p_neg)).mul(&slope).neg();
-                        let ydiff = y_r.clone().add(&y_p_neg);
-
-                        Ok(ydiff.add(&xdiff))
+                        return (x_p_neg, F::one(), F::zero());
                     }
-                }
+                    num.div_unchecked(&denom)
+                };
+
+                // yR - yP - slope (xR - xP)
+                (slope.clone().mul(x_p).add(&y_p_neg), slope.neg(), F::one())
             }
         }
     }
 
     // Returns the addition of P with Q
-    pub fn add(&self, pt_p: &ECPoint<F>, pt_q: &ECPoint<F>) -> ECPoint<F> {
+    // /!\ Fails if P and Q share their abscissa without being equal or opposite,
+    // which can only happen if they are not both on the curve
+    pub fn add(&self, pt_p: &ECPoint<F>, pt_q: &ECPoint<F>) -> Result<ECPoint<F>, ErrorKind> {
         let (x_p, y_p) = match pt_p {
-            ECPoint::PointAtInfinity => return pt_q.clone(),
+            ECPoint::PointAtInfinity => return Ok(pt_q.clone()),
             ECPoint::AffinePoint(x, y) => (x, y),
         };
         let (x_q, y_q) = match pt_q {
-            ECPoint::PointAtInfinity => return pt_p.clone(),
+            ECPoint::PointAtInfinity => return Ok(pt_p.clone()),
             ECPoint::AffinePoint(x, y) => (x, y),
         };
 
         let (a1, a2, a3, a4, _, a6) = self.get_a_invariants();
 
         if x_p == x_q && y_p.clone().add(y_q).add(&a1.clone().mul(x_q)).add(a3) == F::zero() {
-            EllipticCurve::infinity_point()
+            Ok(EllipticCurve::infinity_point())
+        } else if x_p == x_q && y_p != y_q {
+            Err(ErrorKind::InvalidPoint)
         } else {
-            let lambda;
-            let nu;
-            if x_p == x_q {
-                lambda = (a4
-                    .clone()
-                    .add(&x_p.clone().square().zmul(3))
-                    .add(&a2.clone().mul(x_p).zmul(2))
-                    .add(&a1.clone().mul(y_p).neg()))
-                .div(
-                    &a3.clone()
-                        .add(&y_p.clone().zmul(2))
+            // The denominators vanish exactly when P = -Q, handled above, unless the points are
+            // not on the curve
+            let (num_lambda, num_nu, denom) = if x_p == x_q {
+                (
+                    a4.clone()
+                        .add(&x_p.clone().square().zmul(3))
+                        .add(&a2.clone().mul(x_p).double())
+                        .add(&a1.clone().mul(y_p).neg()),
+                    (x_p.clone().square().mul(x_p).neg())
+                        .add(&a4.clone().mul(x_p))
+                        .add(&a6.clone().double())
+                        .add(&a3.clone().mul(y_p).neg()),
+                    a3.clone()
+                        .add(&y_p.clone().double())
                         .add(&a1.clone().mul(x_p)),
-                );
-                nu = (x_p.clone().square().mul(x_p).neg())
-                    .add(&a4.clone().mul(x_p))
-                    .add(&a6.clone().zmul(2))
-                    .add(&a3.clone().mul(y_p).neg())
-                    .div(
-                        &a3.clone()
-                            .add(&y_p.clone().zmul(2))
-                            .add(&a1.clone().mul(x_p)),
-                    );
+                )
             } else {
-                lambda = y_q
-                    .clone()
-                    .add(&y_p.clone().neg())
-                    .div(&x_q.clone().add(&x_p.clone().neg()));
-                nu = y_p
-                    .clone()
-                    .mul(x_q)
-                    .add(&y_q.clone().mul(x_p).neg())
-                    .div(&x_q.clone().add(&x_p.clone().neg()));
-            }
+                (
+                    y_q.clone().add(&y_p.clone().neg()),
+                    y_p.clone().mul(x_q).add(&y_q.clone().mul(x_p).neg()),
+                    x_q.clone().add(&x_p.clone().neg()),
+                )
+            };
+            let lambda = num_lambda
+                .checked_div(&denom)
+                .ok_or(ErrorKind::InvalidPoint)?;
+            let nu = num_nu.checked_div(&denom).ok_or(ErrorKind::InvalidPoint)?;
             let x = a2
                 .clone()
                 .neg()
@@ -226,69 +1153,1773 @@ impl<F: Field + Clone + PartialEq> EllipticCurve<F> {
                 .add(&x.clone().mul(&lambda.add(a1)))
                 .neg();
 
-            ECPoint::AffinePoint(x, y)
+            Ok(ECPoint::AffinePoint(x, y))
         }
     }
 
     // Doubles P
-    pub fn double(&self, pt_p: &ECPoint<F>) -> ECPoint<F> {
+    pub fn double(&self, pt_p: &ECPoint<F>) -> Result<ECPoint<F>, ErrorKind> {
         let (x_p, y_p) = match pt_p {
-            ECPoint::PointAtInfinity => return pt_p.clone(),
+            ECPoint::PointAtInfinity => return Ok(pt_p.clone()),
             ECPoint::AffinePoint(x, y) => (x, y),
         };
 
         let (a1, a2, a3, a4, _, _) = self.get_a_invariants();
 
-        let lambda = &a1
+        // 2y + a1 x + a3
+        let denom = y_p.clone().double().add(&x_p.clone().mul(a1)).add(a3);
+
+        // Vertical tangent: P is a 2-torsion point
+        if denom == F::zero() {
+            return Ok(EllipticCurve::infinity_point());
+        }
+
+        // (3x² + 2 a2 x + a4 - a1 y) / (2y + a1 x + a3)
+        let lambda = &x_p
             .clone()
+            .square()
             .zmul(3)
-            .add(&x_p.clone().mul(a2).zmul(2))
+            .add(&x_p.clone().mul(a2).double())
             .add(&y_p.clone().mul(a1).neg())
             .add(a4)
-            .mul(
-                &y_p.clone()
-                    .zmul(2)
-                    .add(&x_p.clone().mul(a1))
-                    .add(a3)
-                    .invert(),
-            );
+            .mul(&denom.invert());
         let res_x = lambda
             .clone()
             .square()
             .add(&a1.clone().mul(&lambda.clone()))
             .add(&a2.clone().neg())
-            .add(&x_p.clone().zmul(2).neg());
+            .add(&x_p.clone().double().neg());
 
+        // -a1 x' - a3 + lambda (x - x') - y
         let res_y = res_x
             .clone()
             .mul(a1)
             .neg()
             .add(&a3.clone().neg())
-            .add(&res_x.clone().mul(&lambda.clone()))
+            .add(&res_x.clone().mul(&lambda.clone()).neg())
             .add(&x_p.clone().mul(lambda))
             .add(&y_p.clone().neg());
 
-        ECPoint::AffinePoint(res_x, res_y)
+        Ok(ECPoint::AffinePoint(res_x, res_y))
+    }
+
+    // Returns [k]P, using double-and-add
+    // Negative scalars give -[|k|]P, and the loop is iterative so k may be arbitrarily large
+    pub fn mul(&self, k: &Integer, pt_p: &ECPoint<F>) -> Result<ECPoint<F>, ErrorKind> {
+        let k_abs = k.abs_ref().complete();
+        let mut res = EllipticCurve::infinity_point();
+        for i in (0..k_abs.significant_bits()).rev() {
+            res = self.double(&res)?;
+            if k_abs.get_bit(i) {
+                res = self.add(&res, pt_p)?;
+            }
+        }
+        if *k < 0 {
+            res = self.neg_point(&res);
+        }
+        Ok(res)
+    }
+
+    // Returns [k]P with the width-w NAF of k, w between 2 and 8
+    // The odd multiples P, 3P, .., (2^(w-1) - 1)P are precomputed with one doubling and
+    // 2^(w-2) - 1 additions, then the digits cost one addition every w + 1 bits on average: for a
+    // 256-bit scalar, about 128 additions with mul against 50 with w = 5 (see the
+    // mul_window_addition_count test)
+    // Negative scalars give -[|k|]P, as with mul
+    pub fn mul_window(
+        &self,
+        k: &Integer,
+        pt_p: &ECPoint<F>,
+        w: usize,
+    ) -> Result<ECPoint<F>, ErrorKind> {
+        if !(2..=8).contains(&w) {
+            return Err(ErrorKind::InvalidInput(
+                "Window size must be between 2 and 8",
+            ));
+        }
+
+        // Odd multiples [2i + 1]P
+        let pt_2p = self.double(pt_p)?;
+        let mut odd = vec![pt_p.clone()];
+        for i in 1..(1 << (w - 2)) {
+            odd.push(self.add(&odd[i - 1], &pt_2p)?);
+        }
+
+        let mut res = EllipticCurve::infinity_point();
+        for digit in to_wnaf(k, w).iter().rev() {
+            res = self.double(&res)?;
+            if *digit > 0 {
+                res = self.add(&res, &odd[(*digit as usize - 1) / 2])?;
+            } else if *digit < 0 {
+                res = self.sub(&res, &odd[(digit.unsigned_abs() as usize - 1) / 2])?;
+            }
+        }
+        if *k < 0 {
+            res = self.neg_point(&res);
+        }
+        Ok(res)
+    }
+
+    // Precomputes a table of multiples of B for mul_fixed_base, for scalars of at most `bits` bits
+    // With a window of size w the table holds ceil(bits / w) (2^w - 1) points, and a scalar
+    // multiplication costs ceil(bits / w) additions and no doubling: larger windows trade
+    // memory (exponential in w) for speed (linear in 1/w), w = 4 to 8 is usually a good choice
+    pub fn precompute_base(
+        &self,
+        base: &ECPoint<F>,
+        window: usize,
+        bits: u32,
+    ) -> Result<BaseTable<F>, ErrorKind> {
+        if !(1..=16).contains(&window) {
+            return Err(ErrorKind::InvalidInput(
+                "Window size must be between 1 and 16",
+            ));
+        }
+
+        let windows = (bits as usize).div_ceil(window);
+        let mut table = Vec::with_capacity(windows);
+
+        // B_j = [2^(w j)]B
+        let mut pt_b = base.clone();
+        for _ in 0..windows {
+            let mut multiples = Vec::with_capacity((1 << window) - 1);
+            let mut pt_m = pt_b.clone();
+            for _ in 1..(1 << window) {
+                multiples.push(pt_m.clone());
+                pt_m = self.add(&pt_m, &pt_b)?;
+            }
+            // [2^w]B_j is the last multiple plus B_j
+            pt_b = pt_m;
+            table.push(multiples);
+        }
+
+        Ok(BaseTable {
+            window,
+            bits,
+            table,
+        })
+    }
+
+    // Returns [k]B using a table precomputed with precompute_base
+    // /!\ k must be non-negative and fit in the number of bits of the table
+    pub fn mul_fixed_base(
+        &self,
+        table: &BaseTable<F>,
+        k: &Integer,
+    ) -> Result<ECPoint<F>, ErrorKind> {
+        if *k < 0 || k.significant_bits() > table.bits {
+            return Err(ErrorKind::InvalidInput("Scalar does not fit in the table"));
+        }
+
+        let mut res = EllipticCurve::infinity_point();
+        for (j, multiples) in table.table.iter().enumerate() {
+            // j-th window of k
+            let digit = (0..table.window).fold(0usize, |acc, i| {
+                acc | ((k.get_bit((j * table.window + i) as u32) as usize) << i)
+            });
+            if digit != 0 {
+                res = self.add(&res, &multiples[digit - 1])?;
+            }
+        }
+        Ok(res)
+    }
+
+    // Returns [k]P, using the GLV method
+    // phi is an endomorphism acting as [λ] on the subgroup of order n containing P, and the basis
+    // spans the lattice {(a, b) : a + b λ = 0 mod n}, so that n = |a1 b2 - a2 b1|
+    // k is split as k = k1 + k2 λ mod n with k1, k2 about sqrt(n), and [k1]P + [k2]phi(P) is
+    // computed with a single interleaved double-and-add loop
+    pub fn mul_glv(
+        &self,
+        k: &Integer,
+        pt_p: &ECPoint<F>,
+        endomorphism: impl Fn(&ECPoint<F>) -> ECPoint<F>,
+        lattice_basis: &LatticeBasis,
+    ) -> Result<ECPoint<F>, ErrorKind> {
+        if *pt_p == ECPoint::PointAtInfinity {
+            return Ok(EllipticCurve::infinity_point());
+        }
+
+        let (k1, k2) = glv_split(k, lattice_basis)?;
+
+        // Signs are moved to the points
+        let pt_1 = if k1 < 0 {
+            self.invert(pt_p)?
+        } else {
+            pt_p.clone()
+        };
+        let pt_phi = endomorphism(pt_p);
+        let pt_2 = match (&pt_phi, k2 < 0) {
+            (ECPoint::AffinePoint(_, _), true) => self.invert(&pt_phi)?,
+            _ => pt_phi,
+        };
+        let pt_12 = self.add(&pt_1, &pt_2)?;
+        let (k1, k2) = (k1.abs(), k2.abs());
+
+        let mut res = EllipticCurve::infinity_point();
+        for i in (0..k1.significant_bits().max(k2.significant_bits())).rev() {
+            res = self.double(&res)?;
+            match (k1.get_bit(i), k2.get_bit(i)) {
+                (true, true) => res = self.add(&res, &pt_12)?,
+                (true, false) => res = self.add(&res, &pt_1)?,
+                (false, true) => res = self.add(&res, &pt_2)?,
+                (false, false) => {}
+            }
+        }
+        Ok(res)
+    }
+
+    // Returns k in [0, n) such that [k]B = T, where n is the order of B, using baby-step giant-step
+    // Returns None if T is not in the subgroup generated by B
+    // O(sqrt(n)) time and memory: only meant for small subgroups
+    // Needs the std feature for the hash map of baby steps
+    #[cfg(feature = "std")]
+    pub fn discrete_log(
+        &self,
+        base: &ECPoint<F>,
+        target: &ECPoint<F>,
+        order: &Integer,
+    ) -> Option<Integer>
+    where
+        F: Eq + Hash,
+    {
+        if *order < 1 {
+            return None;
+        }
+
+        // m = ceil(sqrt(n))
+        let m = (order - 1u32).complete().sqrt() + 1u32;
+        let steps = m.to_usize()?;
+
+        // Baby steps: [j]B -> j
+        let mut baby = HashMap::with_capacity(steps);
+        let mut pt = ECPoint::PointAtInfinity;
+        for j in 0..steps {
+            baby.entry(pt.clone()).or_insert(j);
+            pt = self.add(&pt, base).ok()?;
+        }
+
+        // Giant steps: T - [i m]B
+        let giant = self.invert(&self.mul(&m, base).ok()?).ok();
+        let mut pt = target.clone();
+        for i in 0..steps {
+            if let Some(j) = baby.get(&pt) {
+                return Some(Integer::from(i) * &m + j);
+            }
+            match &giant {
+                Some(giant) => pt = self.add(&pt, giant).ok()?,
+                // [m]B = 0: the baby steps already cover the subgroup
+                None => return None,
+            }
+        }
+        None
     }
 
     // Returns the inverse of P
-    // /!\ Can not invert zero point
+    // /!\ Can not invert zero point, see neg_point
     pub fn invert(&self, pt_p: &ECPoint<F>) -> Result<ECPoint<F>, ErrorKind> {
-        let (x, y) = match pt_p {
-            ECPoint::PointAtInfinity => return Err(ErrorKind::InvalidInput("P must not be zero")),
-            ECPoint::AffinePoint(x, y) => (x, y),
-        };
-        let (a1, _, a3, _, _, _) = self.get_a_invariants();
-        let new_y = a3.clone().add(&a1.clone().mul(x)).add(y).neg();
+        if pt_p == &ECPoint::PointAtInfinity {
+            return Err(ErrorKind::InvalidInput("P must not be zero"));
+        }
+        Ok(self.neg_point(pt_p))
+    }
+
+    // Returns -P = (x, -(a3 + a1 x + y)), the zero point being its own inverse
+    pub fn neg_point(&self, pt_p: &ECPoint<F>) -> ECPoint<F> {
+        match pt_p {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(x, y) => {
+                let (a1, _, a3, _, _, _) = self.get_a_invariants();
+                let new_y = a3.clone().add(&a1.clone().mul(x)).add(y).neg();
+                ECPoint::AffinePoint(x.clone(), new_y)
+            }
+        }
+    }
+
+    // Returns the difference P - Q
+    pub fn sub(&self, pt_p: &ECPoint<F>, pt_q: &ECPoint<F>) -> Result<ECPoint<F>, ErrorKind> {
+        self.add(pt_p, &self.neg_point(pt_q))
+    }
+
+    // Returns the q-power Frobenius (x^q, y^q) of P, with q the base field order
+    // The coordinates are mapped with Field::frobenius, the zero point is fixed
+    // /!\ The curve must be defined over the base field
+    pub fn frobenius(&self, pt_p: &ECPoint<F>) -> ECPoint<F> {
+        match pt_p {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(x, y) => ECPoint::AffinePoint(x.frobenius(), y.frobenius()),
+        }
+    }
+
+    // Returns the anti-trace [k]P - Tr(P) of P, see trace_map, where k is the embedding degree
+    // For P of order n, the result is in the trace zero subgroup E[n] ∩ ker(π - q), i.e. the G2
+    // expected by ate_pairing. It is zero when P is in G1 = E[n] ∩ ker(π - 1)
+    // /!\ The curve must be defined over the base field
+    pub fn map_to_trace_zero(
+        &self,
+        pt_p: &ECPoint<F>,
+        embedding_degree: u32,
+    ) -> Result<ECPoint<F>, ErrorKind> {
+        let trace = self.trace_map(pt_p, embedding_degree)?;
+        self.sub(&self.mul(&Integer::from(embedding_degree), pt_p)?, &trace)
+    }
+
+    // Returns the trace Tr(P) = P + π(P) + ... + π^(k-1)(P), k being the embedding degree
+    // For P defined over Fq^k, Tr(P) is fixed by π, i.e. defined over the base field: for P of
+    // order n, Tr(P) is in G1 = E[n] ∩ ker(π - 1), and it is zero when P is in the trace zero
+    // subgroup E[n] ∩ ker(π - q)
+    // /!\ The curve must be defined over the base field
+    pub fn trace_map(
+        &self,
+        pt_p: &ECPoint<F>,
+        embedding_degree: u32,
+    ) -> Result<ECPoint<F>, ErrorKind> {
+        let mut trace = ECPoint::PointAtInfinity;
+        let mut pt_pi = pt_p.clone();
+        for _ in 0..embedding_degree {
+            trace = self.add(&trace, &pt_pi)?;
+            pt_pi = self.frobenius(&pt_pi);
+        }
+        Ok(trace)
+    }
+
+    // Returns h(ψ)(P) = [c0]P + [c1]ψ(P) + .. + [cd]ψ^d(P) for the coefficients (c0, .., cd) of h,
+    // which may be negative
+    // ψ is an endomorphism of the curve, on G2 usually the untwist-Frobenius-twist map. When h(ψ)
+    // is a multiple of the cofactor on the curve, e.g. h = (x² - x - 1) + (x - 1) ψ + 2 ψ² for
+    // BLS12 curves (Budroni-Pintore), the result lies in the order r subgroup like [cofactor]P,
+    // for a fraction of the cost since the coefficients are much smaller than the cofactor
+    pub fn clear_cofactor_g2<E>(
+        &self,
+        pt_p: &ECPoint<F>,
+        endomorphism: E,
+        coefficients: &[Integer],
+    ) -> Result<ECPoint<F>, ErrorKind>
+    where
+        E: Fn(&ECPoint<F>) -> ECPoint<F>,
+    {
+        // Horner scheme: R = ψ(R) + [ci]P, from cd down to c0
+        let mut res = ECPoint::PointAtInfinity;
+        for c in coefficients.iter().rev() {
+            res = self.add(&endomorphism(&res), &self.mul(c, pt_p)?)?;
+        }
+        Ok(res)
+    }
+}
+
+// Whether the non-zero polynomial h has a root in F, i.e. whether gcd(x^q - x, h) is not constant
+// with q the order of F. x^q is computed modulo h by square and multiply
+fn has_root<F: Field + Clone + PartialEq>(h: &Polynomial<F>) -> Result<bool, ErrorKind> {
+    let q = F::order();
+    let mut x_q = Polynomial::constant(F::one());
+    for i in (0..q.significant_bits()).rev() {
+        x_q = x_q.square().rem(h)?;
+        if q.get_bit(i) {
+            x_q = x_q.mul(&Polynomial::x()).rem(h)?;
+        }
+    }
+
+    let (mut u, mut v) = (h.clone(), x_q.sub(&Polynomial::x()));
+    while !v.is_zero() {
+        let r = u.rem(&v)?;
+        u = v;
+        v = r;
+    }
+    Ok(u.degree() != Some(0))
+}
+
+// Elements 0, 1, .. p - 1 of a prime field F of characteristic p
+fn prime_field_elements<F: Field + Clone>() -> impl Iterator<Item = F> {
+    let p = F::characteristic();
+    let mut i = Integer::new();
+    core::iter::successors(Some(F::zero()), |x| Some(x.clone().add(&F::one()))).take_while(
+        move |_| {
+            i += 1;
+            i <= p
+        },
+    )
+}
 
-        Ok(ECPoint::AffinePoint(x.clone(), new_y))
+// Little endian width-w non-adjacent form of |k|: the non-zero digits are odd, lie in
+// (-2^(w-1), 2^(w-1)), and any w consecutive digits have at most one of them
+pub(crate) fn to_wnaf(k: &Integer, w: usize) -> Vec<i32> {
+    let modulus = 1u32 << w;
+    let mut k = k.clone().abs();
+    let mut naf = Vec::new();
+    while k != 0 {
+        if k.is_odd() {
+            let mut digit = k.mod_u(modulus) as i32;
+            if digit >= (modulus / 2) as i32 {
+                digit -= modulus as i32;
+            }
+            k -= digit;
+            naf.push(digit);
+        } else {
+            naf.push(0);
+        }
+        k >>= 1;
     }
+    naf
+}
+
+// Returns (k1, k2) with k = k1 + k2 λ mod n and k1, k2 about sqrt(n), see mul_glv
+// (k, 0) is rounded to the closest vector c1 (a1, b1) + c2 (a2, b2) of the lattice, with the
+// signed determinant det = a1 b2 - a2 b1 = ±n
+pub(crate) fn glv_split(
+    k: &Integer,
+    lattice_basis: &LatticeBasis,
+) -> Result<(Integer, Integer), ErrorKind> {
+    let ((a1, b1), (a2, b2)) = lattice_basis;
+    let det = a1.clone() * b2 - a2.clone() * b1;
+    if det == 0 {
+        return Err(ErrorKind::InvalidInput(
+            "Lattice basis must be linearly independent",
+        ));
+    }
+
+    // c1 = round(b2 k / det), c2 = round(-b1 k / det)
+    let c1 = (b2.clone() * k).div_rem_round(det.clone()).0;
+    let c2 = (-b1.clone() * k).div_rem_round(det).0;
+
+    // (k1, k2) = (k, 0) - c1 (a1, b1) - c2 (a2, b2)
+    let k1 = k.clone() - c1.clone() * a1 - c2.clone() * a2;
+    let k2 = -(c1 * b1) - c2 * b2;
+    Ok((k1, k2))
 }
 
 // Point on a curve
 impl<F: Field + Clone + PartialEq> ECPoint<F> {
     // New point from affine coords
+    // /!\ The point is not checked to be on any curve, see EllipticCurve::point
     pub fn new_affine(x: F, y: F) -> Self {
         ECPoint::AffinePoint(x, y)
     }
+
+    /// Affine coords (x, y), None for the zero point
+    ///
+    /// ```
+    /// # use pairings::{elliptic_curve::ECPoint, field::Field};
+    /// # use rug::Integer;
+    /// # // Toy field F7, only what the example needs
+    /// # #[derive(Clone, Debug, PartialEq)]
+    /// # struct F7(u64);
+    /// # impl Field for F7 {
+    /// #     fn zero() -> Self { F7(0) }
+    /// #     fn one() -> Self { F7(1) }
+    /// #     fn ct_eq(&self, y: &Self) -> bool { self.0 == y.0 }
+    /// #     fn add(self, y: &Self) -> Self { F7((self.0 + y.0) % 7) }
+    /// #     fn mul(self, y: &Self) -> Self { F7(self.0 * y.0 % 7) }
+    /// #     fn zmul(self, y: i64) -> Self { F7((self.0 as i64 * y).rem_euclid(7) as u64) }
+    /// #     fn pow(self, _: &Integer) -> Self { unimplemented!() }
+    /// #     fn zpow(self, _: i64) -> Self { unimplemented!() }
+    /// #     fn div(self, _: &Self) -> Self { unimplemented!() }
+    /// #     fn square(self) -> Self { F7(self.0 * self.0 % 7) }
+    /// #     fn sqrt(self) -> Self { unimplemented!() }
+    /// #     fn invert(self) -> Self { unimplemented!() }
+    /// #     fn neg(self) -> Self { F7((7 - self.0) % 7) }
+    /// #     fn degree() -> u32 { 1 }
+    /// #     fn base_order() -> Integer { Integer::from(7) }
+    /// #     fn characteristic() -> Integer { Integer::from(7) }
+    /// #     fn random_element() -> Self { unimplemented!() }
+    /// # }
+    /// let pt = ECPoint::new_affine(F7(3), F7(5));
+    /// assert_eq!(pt.xy(), Some((&F7(3), &F7(5))));
+    /// assert_eq!(pt.x(), Some(&F7(3)));
+    /// assert_eq!(pt.y(), Some(&F7(5)));
+    ///
+    /// let zero = ECPoint::<F7>::PointAtInfinity;
+    /// assert_eq!(zero.xy(), None);
+    /// assert_eq!(zero.x(), None);
+    /// ```
+    pub fn xy(&self) -> Option<(&F, &F)> {
+        match self {
+            ECPoint::AffinePoint(x, y) => Some((x, y)),
+            ECPoint::PointAtInfinity => None,
+        }
+    }
+
+    /// Abscissa, None for the zero point
+    pub fn x(&self) -> Option<&F> {
+        self.xy().map(|(x, _)| x)
+    }
+
+    /// Ordinate, None for the zero point
+    pub fn y(&self) -> Option<&F> {
+        self.xy().map(|(_, y)| y)
+    }
+
+    /// Same point with its coords mapped into an extension G of F by `embed`
+    // e.g. a G1 point brought to the field of a G2 point to pair them, see
+    // EllipticCurve::base_change
+    ///
+    /// ```
+    /// # use pairings::{elliptic_curve::ECPoint, field::Field};
+    /// # use rug::Integer;
+    /// # // Toy field F7, only what the example needs
+    /// # #[derive(Clone, Debug, PartialEq)]
+    /// # struct F7(u64);
+    /// # impl Field for F7 {
+    /// #     fn zero() -> Self { F7(0) }
+    /// #     fn one() -> Self { F7(1) }
+    /// #     fn ct_eq(&self, y: &Self) -> bool { self.0 == y.0 }
+    /// #     fn add(self, y: &Self) -> Self { F7((self.0 + y.0) % 7) }
+    /// #     fn mul(self, y: &Self) -> Self { F7(self.0 * y.0 % 7) }
+    /// #     fn zmul(self, y: i64) -> Self { F7((self.0 as i64 * y).rem_euclid(7) as u64) }
+    /// #     fn pow(self, _: &Integer) -> Self { unimplemented!() }
+    /// #     fn zpow(self, _: i64) -> Self { unimplemented!() }
+    /// #     fn div(self, _: &Self) -> Self { unimplemented!() }
+    /// #     fn square(self) -> Self { F7(self.0 * self.0 % 7) }
+    /// #     fn sqrt(self) -> Self { unimplemented!() }
+    /// #     fn invert(self) -> Self { unimplemented!() }
+    /// #     fn neg(self) -> Self { F7((7 - self.0) % 7) }
+    /// #     fn degree() -> u32 { 1 }
+    /// #     fn base_order() -> Integer { Integer::from(7) }
+    /// #     fn characteristic() -> Integer { Integer::from(7) }
+    /// #     fn random_element() -> Self { unimplemented!() }
+    /// # }
+    /// // Any field morphism works, here the identity of F7
+    /// let pt = ECPoint::new_affine(F7(3), F7(5)).embed(|c: &F7| c.clone());
+    /// assert_eq!(pt.xy(), Some((&F7(3), &F7(5))));
+    /// assert_eq!(
+    ///     ECPoint::<F7>::PointAtInfinity.embed(|c: &F7| c.clone()),
+    ///     ECPoint::PointAtInfinity
+    /// );
+    /// ```
+    pub fn embed<G: Field + Clone>(&self, embed: impl Fn(&F) -> G) -> ECPoint<G> {
+        match self {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(x, y) => ECPoint::AffinePoint(embed(x), embed(y)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "hash")]
+    use crate::hash::{short_dst, Sha256};
+    use crate::test_fields::{take_op_counts, Counted, Fp, Fp2, Fq, P256};
+
+    type F = Fp<103>;
+
+    // y² = x³ + x over F103, with 104 = 8 × 13 points
+    fn curve() -> EllipticCurve<F> {
+        EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::one(),
+            F::zero(),
+            F::zero(),
+        ))
+        .unwrap()
+        .with_order(Integer::from(13))
+        .with_cofactor(Integer::from(8))
+    }
+
+    type K = Fp2<103>;
+
+    // The same curve over F103², whose points form (Z/104)²: E[13] is defined over it, the
+    // Frobenius acts on it with eigenvalues 1 (G1, points over F103) and q = -1 mod 13 (G2)
+    fn curve_k() -> EllipticCurve<K> {
+        EllipticCurve::new((
+            K::zero(),
+            K::zero(),
+            K::zero(),
+            K::one(),
+            K::zero(),
+            K::zero(),
+        ))
+        .unwrap()
+    }
+
+    // Point of G1 and its image (-x, iy) in G2 by the distortion map
+    fn g1_g2_points() -> (ECPoint<K>, ECPoint<K>) {
+        let pt = curve().random_point_in_subgroup(&Integer::from(8)).unwrap();
+        let (x, y) = pt.xy().unwrap();
+        let embed = |x: &F| Fp2(x.clone(), F::zero());
+        let distorted = ECPoint::AffinePoint(embed(x).neg(), embed(y).mul(&K::new(0, 1)));
+        (pt.embed(embed), distorted)
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let curve = curve();
+        let json = serde_json::to_string(&curve).unwrap();
+        let decoded: EllipticCurve<F> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, curve);
+        assert_eq!(decoded.order(), curve.order());
+        assert_eq!(decoded.cofactor(), curve.cofactor());
+
+        // The SSWU constant is kept, and checked against the curve
+        let sswu_curve = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::new(2),
+            F::zero(),
+            F::new(3),
+        ))
+        .unwrap()
+        .with_sswu_z()
+        .unwrap();
+        let json = serde_json::to_string(&sswu_curve).unwrap();
+        let decoded: EllipticCurve<F> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.sswu_z(), sswu_curve.sswu_z());
+        let z = serde_json::to_string(sswu_curve.sswu_z().unwrap()).unwrap();
+        let json = json.replace(&format!("\"sswu_z\":{z}"), "\"sswu_z\":1");
+        assert!(serde_json::from_str::<EllipticCurve<F>>(&json).is_err());
+
+        for pt in [curve.random_point().unwrap(), ECPoint::PointAtInfinity] {
+            let json = serde_json::to_string(&pt).unwrap();
+            let mut deserializer = serde_json::Deserializer::from_str(&json);
+            assert_eq!(
+                curve.point_seed().deserialize(&mut deserializer).unwrap(),
+                pt
+            );
+        }
+
+        // Points off the curve are rejected
+        let json = serde_json::to_string(&ECPoint::AffinePoint(F::new(1), F::new(1))).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        assert!(curve.point_seed().deserialize(&mut deserializer).is_err());
+
+        // So are curves in characteristic 3
+        let curve = EllipticCurve::new_long_weierstrass((
+            Fp::<3>::zero(),
+            Fp::zero(),
+            Fp::zero(),
+            Fp::one(),
+            Fp::zero(),
+            Fp::one(),
+        ));
+        let json = serde_json::to_string(&curve).unwrap();
+        assert!(serde_json::from_str::<EllipticCurve<Fp<3>>>(&json).is_err());
+
+        // Extension field elements and points over them
+        let curve = EllipticCurve::<Fp2<103>>::new((
+            Fp2::zero(),
+            Fp2::zero(),
+            Fp2::zero(),
+            Fp2::one(),
+            Fp2::zero(),
+            Fp2::zero(),
+        ))
+        .unwrap();
+        let pt = curve.random_point().unwrap();
+        let json = serde_json::to_string(&pt).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        assert_eq!(
+            curve.point_seed().deserialize(&mut deserializer).unwrap(),
+            pt
+        );
+        let x = Fp2::new(5, 7);
+        let json = serde_json::to_string(&x).unwrap();
+        assert_eq!(serde_json::from_str::<Fp2<103>>(&json).unwrap(), x);
+    }
+
+    #[test]
+    fn new_rejects_small_characteristic() {
+        // What the deserializer relies on to reject the curve above
+        fn new<F: Field + Clone + PartialEq>() -> Result<EllipticCurve<F>, ErrorKind> {
+            EllipticCurve::new((
+                F::zero(),
+                F::zero(),
+                F::zero(),
+                F::one(),
+                F::zero(),
+                F::one(),
+            ))
+        }
+        let err = ErrorKind::InvalidInput("Curves in characteristic 2 or 3 are not supported");
+        assert_eq!(new::<Fp<2>>(), Err(err.clone()));
+        assert_eq!(new::<Fp<3>>(), Err(err));
+        assert!(new::<Fp<5>>().is_ok());
+    }
+
+    #[test]
+    fn add_degenerate_input() {
+        let curve = curve();
+        let pt = curve.point(F::new(1), F::new(38)).unwrap();
+        // Same x, y neither equal nor opposite: not both on the curve
+        let off = ECPoint::AffinePoint(F::new(1), F::new(39));
+        assert_eq!(curve.add(&pt, &off), Err(ErrorKind::InvalidPoint));
+        assert_eq!(
+            curve.add(&pt, &curve.neg_point(&pt)),
+            Ok(ECPoint::PointAtInfinity)
+        );
+    }
+
+    #[test]
+    fn random_point_in_subgroup() {
+        let curve = curve();
+        for _ in 0..16 {
+            let pt = curve.random_point_in_subgroup(&Integer::from(8)).unwrap();
+            assert_ne!(pt, ECPoint::PointAtInfinity);
+            assert!(curve.is_on_curve(&pt));
+            assert!(curve.is_in_subgroup(&pt).unwrap());
+            assert_eq!(
+                curve.mul(&Integer::from(13), &pt),
+                Ok(ECPoint::PointAtInfinity)
+            );
+        }
+        // The group order kills every point
+        assert!(curve.random_point_in_subgroup(&Integer::from(104)).is_err());
+    }
+
+    #[test]
+    fn mul_glv_matches_mul() {
+        // y² = x³ + 6 over F103, with 91 = 7 × 13 points. (x, y) ↦ (ζx, y) with ζ = 46 a cube
+        // root of unity acts as [3] on the subgroup of order 13, λ = 3 being a root of
+        // λ² + λ + 1 mod 13
+        let curve = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::new(6),
+        ))
+        .unwrap();
+        let zeta = F::new(46);
+        let endomorphism = |pt: &ECPoint<F>| match pt {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(x, y) => ECPoint::AffinePoint(x.clone().mul(&zeta), y.clone()),
+        };
+        let basis = (
+            (Integer::from(-3), Integer::from(1)),
+            (Integer::from(1), Integer::from(4)),
+        );
+        let pt = curve.random_point_in_subgroup(&Integer::from(7)).unwrap();
+        assert_eq!(
+            endomorphism(&pt),
+            curve.mul(&Integer::from(3), &pt).unwrap()
+        );
+
+        // Every sign combination of (k1, k2) occurs for k in [-30, 30]
+        // The basis has determinant -13, and k1, k2 have about half the 4 bits of n = 13
+        let big = Integer::from(1) << 80u32;
+        for k in (-30..=30)
+            .map(Integer::from)
+            .chain([big.clone() + 7u32, -big])
+        {
+            let (k1, k2) = glv_split(&k, &basis).unwrap();
+            let diff = Integer::from(&k1 + &k2 * 3u32) - &k;
+            assert!(diff.is_divisible_u(13));
+            assert!(k1.significant_bits() <= 3 && k2.significant_bits() <= 3);
+            assert_eq!(
+                curve.mul_glv(&k, &pt, endomorphism, &basis),
+                curve.mul(&k, &pt),
+                "k = {k}"
+            );
+        }
+        assert_eq!(
+            curve.mul_glv(
+                &Integer::from(5),
+                &ECPoint::PointAtInfinity,
+                endomorphism,
+                &basis
+            ),
+            Ok(ECPoint::PointAtInfinity)
+        );
+    }
+
+    #[test]
+    fn mul_fixed_base_matches_mul() {
+        let curve = curve();
+        let pt = curve.random_point().unwrap();
+        let full = (Integer::from(1) << 64u32) - 1u32;
+        for window in [1, 4, 5] {
+            let table = curve.precompute_base(&pt, window, 64).unwrap();
+            for k in [
+                Integer::new(),
+                Integer::from(1),
+                Integer::from(104),
+                Integer::from(0xdead_beef_u64),
+                full.clone(),
+            ] {
+                assert_eq!(curve.mul_fixed_base(&table, &k), curve.mul(&k, &pt));
+            }
+            assert!(curve.mul_fixed_base(&table, &Integer::from(-1)).is_err());
+            assert!(curve
+                .mul_fixed_base(&table, &(full.clone() + 1u32))
+                .is_err());
+        }
+        assert!(curve.precompute_base(&pt, 0, 64).is_err());
+        assert!(curve.precompute_base(&pt, 17, 64).is_err());
+    }
+
+    #[test]
+    fn line_vertical_and_tangent_cases() {
+        let curve = curve();
+        let pt_p = curve.point(F::new(1), F::new(38)).unwrap();
+        let pt_q = curve.point(F::new(3), F::new(37)).unwrap();
+        let pt_r = curve.point(F::new(6), F::new(4)).unwrap();
+        let x_r = F::new(6);
+
+        // Vertical lines xR - xP, for Q = -P and Q = 0
+        let vertical = x_r.clone().add(&F::one().neg());
+        let line = |p: &ECPoint<F>, q: &ECPoint<F>| curve.line(p, q, &pt_r).unwrap();
+        assert_eq!(line(&pt_p, &curve.neg_point(&pt_p)), vertical);
+        assert_eq!(line(&pt_p, &ECPoint::PointAtInfinity), vertical);
+        assert_eq!(line(&ECPoint::PointAtInfinity, &pt_p), vertical);
+        assert_eq!(
+            line(&ECPoint::PointAtInfinity, &ECPoint::PointAtInfinity),
+            F::one()
+        );
+
+        // The tangent at the 2-torsion point (0, 0) is the vertical line x = 0
+        let pt_t = curve.point(F::zero(), F::zero()).unwrap();
+        assert_eq!(line(&pt_t, &pt_t), x_r);
+
+        // Other lines vanish at P, Q and -(P + Q)
+        for (p, q) in [(&pt_p, &pt_q), (&pt_p, &pt_p)] {
+            let sum = curve.add(p, q).unwrap();
+            for pt in [p.clone(), q.clone(), curve.neg_point(&sum)] {
+                assert_eq!(curve.line(p, q, &pt), Ok(F::zero()));
+            }
+            assert_ne!(line(p, q), F::zero());
+        }
+
+        assert!(curve.line(&pt_p, &pt_q, &ECPoint::PointAtInfinity).is_err());
+    }
+
+    #[test]
+    fn count_points_small_curves() {
+        assert_eq!(curve().count_points(), Ok(Integer::from(104)));
+
+        // y² = x³ + x + 1 over F5: x = 0, 2, 3, 4 give two points each, x = 1 none
+        let curve = EllipticCurve::new((
+            Fp::<5>::zero(),
+            Fp::zero(),
+            Fp::zero(),
+            Fp::one(),
+            Fp::zero(),
+            Fp::one(),
+        ))
+        .unwrap();
+        assert_eq!(curve.count_points(), Ok(Integer::from(9)));
+
+        // Extension fields are rejected
+        let curve = EllipticCurve::new((
+            Fp2::<103>::zero(),
+            Fp2::zero(),
+            Fp2::zero(),
+            Fp2::one(),
+            Fp2::zero(),
+            Fp2::zero(),
+        ))
+        .unwrap();
+        assert!(curve.count_points().is_err());
+        assert!(curve.trace_of_frobenius().is_err());
+        assert!(curve.is_supersingular().is_err());
+    }
+
+    #[test]
+    fn trace_of_frobenius_and_hasse_bound() {
+        let curve = curve();
+        assert_eq!(curve.trace_of_frobenius(), Ok(Integer::new()));
+        assert_eq!(
+            curve.frobenius_charpoly(),
+            Ok((Integer::new(), Integer::from(103)))
+        );
+
+        // y² = x³ + x + 1 over F5 has 9 = 5 + 1 - t points
+        let curve_f5 = EllipticCurve::new((
+            Fp::<5>::zero(),
+            Fp::zero(),
+            Fp::zero(),
+            Fp::one(),
+            Fp::zero(),
+            Fp::one(),
+        ))
+        .unwrap();
+        let trace = curve_f5.trace_of_frobenius().unwrap();
+        assert_eq!(trace, -3);
+        assert!(curve_f5.check_trace(&trace).is_ok());
+
+        // t² <= 4 × 103 = 412 holds up to |t| = 20
+        for t in [0, 20, -20] {
+            assert!(curve.check_trace(&Integer::from(t)).is_ok());
+        }
+        for t in [21, -21, 104] {
+            assert!(curve.check_trace(&Integer::from(t)).is_err());
+        }
+    }
+
+    #[test]
+    fn is_supersingular_cases() {
+        // y² = x³ + x over F103, p = 3 mod 4: t = 0
+        assert_eq!(curve().is_supersingular(), Ok(true));
+        // y² = x³ + 2 over F103, p = 1 mod 3: 117 points, t = -13
+        let curve = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::new(2),
+        ))
+        .unwrap();
+        assert_eq!(curve.trace_of_frobenius(), Ok(Integer::from(-13)));
+        assert_eq!(curve.is_supersingular(), Ok(false));
+        // Extension fields are rejected
+        assert!(curve_k().is_supersingular().is_err());
+    }
+
+    #[test]
+    fn points_of_small_curves() {
+        type F5 = Fp<5>;
+        // Points of y² = x³ + a4 x + a6 over F5, compared with the hand-counted ones
+        let check = |a4: i64, a6: i64, coords: &[(i64, i64)]| {
+            let curve = EllipticCurve::new((
+                F5::zero(),
+                F5::zero(),
+                F5::zero(),
+                F5::new(a4),
+                F5::zero(),
+                F5::new(a6),
+            ))
+            .unwrap();
+            let found: Vec<_> = curve.points().collect();
+            assert_eq!(found[0], ECPoint::PointAtInfinity);
+            assert_eq!(found.len(), coords.len() + 1);
+            for (x, y) in coords {
+                assert!(found.contains(&ECPoint::AffinePoint(F5::new(*x), F5::new(*y))));
+            }
+            // By increasing abscissa
+            let xs: Vec<_> = found[1..].iter().map(|pt| pt.x().unwrap()).collect();
+            assert!(xs.windows(2).all(|w| w[0] <= w[1]));
+        };
+
+        // y² = x³ + x + 1: two ordinates for x = 0, 2, 3, 4 and none for x = 1
+        check(
+            1,
+            1,
+            &[
+                (0, 1),
+                (0, 4),
+                (2, 1),
+                (2, 4),
+                (3, 1),
+                (3, 4),
+                (4, 2),
+                (4, 3),
+            ],
+        );
+        // y² = x³ + x: the roots x = 0, 2, 3 each give a single point, of order 2
+        check(1, 0, &[(0, 0), (2, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn two_and_three_torsion() {
+        // x² + 1 has no root mod 103, (0, 0) is the only point of order 2
+        let curve = curve();
+        assert_eq!(
+            curve.two_torsion(),
+            [
+                ECPoint::PointAtInfinity,
+                curve.point(F::zero(), F::zero()).unwrap()
+            ]
+        );
+        // The full 2-torsion of y² = x³ + x is defined over F5
+        let curve_f5 = EllipticCurve::new((
+            Fp::<5>::zero(),
+            Fp::zero(),
+            Fp::zero(),
+            Fp::one(),
+            Fp::zero(),
+            Fp::zero(),
+        ))
+        .unwrap();
+        assert_eq!(curve_f5.two_torsion().len(), 4);
+
+        // Against the points killed by 2 and 3, for y² = x³ + x, y² = x³ + 2 and
+        // y² + xy + 3y = x³ + 2x² + 4x + 5 over F103
+        for coeffs in [(0, 0, 0, 1, 0, 0), (0, 0, 0, 0, 0, 2), (1, 2, 3, 4, 0, 5)] {
+            let (a1, a2, a3, a4, a5, a6) = coeffs;
+            let curve = EllipticCurve::new((
+                F::new(a1),
+                F::new(a2),
+                F::new(a3),
+                F::new(a4),
+                F::new(a5),
+                F::new(a6),
+            ))
+            .unwrap();
+            let killed_by = |n: i64| -> Vec<ECPoint<F>> {
+                curve
+                    .points()
+                    .filter(|pt| curve.mul(&Integer::from(n), pt) == Ok(ECPoint::PointAtInfinity))
+                    .collect()
+            };
+            assert_eq!(curve.two_torsion(), killed_by(2), "{coeffs:?}");
+            assert_eq!(curve.three_torsion(), killed_by(3), "{coeffs:?}");
+        }
+    }
+
+    #[test]
+    fn division_polynomials() {
+        let poly = |coeffs: &[i64]| Polynomial::new(coeffs.iter().map(|c| F::new(*c)).collect());
+        // y² = x³ + ax + b: ψ3 = 3x⁴ + 6ax² + 12bx - a²,
+        // ψ4 / ψ2 = 2(x⁶ + 5ax⁴ + 20bx³ - 5a²x² - 4abx - 8b² - a³)
+        let curve = curve();
+        assert_eq!(curve.division_polynomial(0), Polynomial::zero());
+        assert_eq!(curve.division_polynomial(1), Polynomial::constant(F::one()));
+        assert_eq!(curve.division_polynomial(2), Polynomial::constant(F::one()));
+        assert_eq!(curve.division_polynomial(3), poly(&[-1, 0, 6, 0, 3]));
+        assert_eq!(
+            curve.division_polynomial(4),
+            poly(&[-2, 0, -10, 0, 10, 0, 2])
+        );
+        let curve_b2 = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::new(2),
+        ))
+        .unwrap();
+        assert_eq!(curve_b2.division_polynomial(3), poly(&[0, 24, 0, 0, 3]));
+        assert_eq!(
+            curve_b2.division_polynomial(4),
+            poly(&[-64, 0, 0, 80, 0, 0, 2])
+        );
+
+        // ψn(P) = 0 iff [n]P = 0, for P not of order 2 when n is even
+        let curve_long = EllipticCurve::new((
+            F::one(),
+            F::new(2),
+            F::new(3),
+            F::new(4),
+            F::zero(),
+            F::new(5),
+        ))
+        .unwrap();
+        for curve in [&curve, &curve_b2, &curve_long] {
+            let two_torsion = curve.two_torsion();
+            for n in 1..=9u32 {
+                let psi = curve.division_polynomial(n);
+                for pt in curve.points().skip(1) {
+                    if n % 2 == 0 && two_torsion.contains(&pt) {
+                        continue;
+                    }
+                    let (x, _) = pt.xy().unwrap();
+                    assert_eq!(
+                        psi.evaluate(x) == F::zero(),
+                        curve.mul(&Integer::from(n), &pt) == Ok(ECPoint::PointAtInfinity),
+                        "n = {n}, P = {pt:?}"
+                    );
+                }
+            }
+            // The 3-torsion points are those of the roots of ψ3
+            let psi_3 = curve.division_polynomial(3);
+            for pt in curve.three_torsion().iter().skip(1) {
+                assert_eq!(psi_3.evaluate(pt.x().unwrap()), F::zero());
+            }
+        }
+        // y² = x³ + 2 has 117 = 9 × 13 points, 3 divides its order
+        assert!(curve_b2.three_torsion().len() > 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_agrees_with_equality() {
+        use std::collections::HashSet;
+
+        // 104 = 1, 141 = 38 and 106 = 3 mod 103
+        let points: HashSet<ECPoint<F>> = [
+            ECPoint::AffinePoint(F::new(1), F::new(38)),
+            ECPoint::AffinePoint(Fp(104), Fp(141)),
+            ECPoint::AffinePoint(F::new(3), F::new(37)),
+            ECPoint::AffinePoint(Fp(106), F::new(37)),
+            ECPoint::PointAtInfinity,
+            ECPoint::PointAtInfinity,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(points.len(), 3);
+
+        let elements: HashSet<F> = [Fp(3), Fp(106), F::new(3), F::new(-100)]
+            .into_iter()
+            .collect();
+        assert_eq!(elements.len(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn discrete_log_recovers_scalar() {
+        let curve = curve();
+        let order = Integer::from(13);
+        let pt_p = curve.random_point_in_subgroup(&Integer::from(8)).unwrap();
+        for k in 0..13 {
+            let k = Integer::from(k);
+            let target = curve.mul(&k, &pt_p).unwrap();
+            assert_eq!(curve.discrete_log(&pt_p, &target, &order), Some(k));
+        }
+
+        // The 2-torsion point (0, 0) is not a multiple of P
+        let pt_t = curve.point(F::zero(), F::zero()).unwrap();
+        assert_eq!(curve.discrete_log(&pt_p, &pt_t, &order), None);
+        assert_eq!(curve.discrete_log(&pt_p, &pt_p, &Integer::new()), None);
+
+        // (3, 37) has order 104
+        let pt_g = curve.point(F::new(3), F::new(37)).unwrap();
+        let order = Integer::from(104);
+        for k in [0, 1, 51, 52, 103] {
+            let k = Integer::from(k);
+            let target = curve.mul(&k, &pt_g).unwrap();
+            assert_eq!(curve.discrete_log(&pt_g, &target, &order), Some(k));
+        }
+    }
+
+    #[test]
+    fn map_to_trace_zero_lands_in_g2() {
+        let curve = curve_k();
+        let (q, r) = (Integer::from(103), Integer::from(13));
+        let (pt_p, pt_q) = g1_g2_points();
+
+        // Trace zero points are only multiplied by k = 2, G1 is killed
+        assert_eq!(
+            curve.map_to_trace_zero(&pt_q, 2),
+            curve.mul(&Integer::from(2), &pt_q)
+        );
+        assert_eq!(
+            curve.map_to_trace_zero(&pt_p, 2),
+            Ok(ECPoint::PointAtInfinity)
+        );
+
+        // Any point of E[13] is sent to the eigenspace of q
+        for _ in 0..8 {
+            let pt = curve.mul_by_cofactor(&curve.random_point().unwrap(), &Integer::from(64));
+            let pt = curve.map_to_trace_zero(&pt.unwrap(), 2).unwrap();
+            assert_eq!(curve.mul(&r, &pt), Ok(ECPoint::PointAtInfinity));
+            assert_eq!(curve.frobenius(&pt), curve.mul(&q, &pt).unwrap());
+        }
+    }
+
+    #[test]
+    fn trace_map_cases() {
+        let curve = curve_k();
+        let (pt_p, pt_q) = g1_g2_points();
+        assert_eq!(curve.trace_map(&pt_q, 2), Ok(ECPoint::PointAtInfinity));
+        assert_eq!(
+            curve.trace_map(&pt_p, 2),
+            curve.mul(&Integer::from(2), &pt_p)
+        );
+
+        // The trace of any point is fixed by π
+        for _ in 0..8 {
+            let pt = curve.trace_map(&curve.random_point().unwrap(), 2).unwrap();
+            assert_eq!(curve.frobenius(&pt), pt);
+        }
+    }
+
+    #[test]
+    fn transform_is_isomorphism() {
+        // y² + xy + 3y = x³ + 2x² + 4x + 5 over F103
+        let curve = EllipticCurve::new((
+            F::one(),
+            F::new(2),
+            F::new(3),
+            F::new(4),
+            F::zero(),
+            F::new(5),
+        ))
+        .unwrap()
+        .with_order(Integer::from(7));
+        let n = curve.count_points().unwrap();
+
+        // Short Weierstrass model: s = -a1 / 2, r = -(a1² + 4 a2) / 12, t = -(a3 + r a1) / 2
+        let half = F::new(2).invert();
+        let s = half.clone().neg();
+        let r = F::new(9).div(&F::new(12)).neg();
+        let t = F::new(3).add(&r).mul(&half).neg();
+        for (u, r, s, t) in [
+            (F::one(), r.clone(), s.clone(), t.clone()),
+            (F::new(2), F::new(5), F::new(7), F::new(11)),
+        ] {
+            let (image, map) = curve.transform(&u, &r, &s, &t).unwrap();
+            assert_eq!(image.count_points(), Ok(n.clone()));
+            assert_eq!(image.order(), curve.order());
+            assert_eq!(map(&ECPoint::PointAtInfinity), ECPoint::PointAtInfinity);
+            for _ in 0..8 {
+                let pt_p = curve.random_point().unwrap();
+                let pt_q = curve.random_point().unwrap();
+                assert!(image.is_on_curve(&map(&pt_p)));
+                assert_eq!(
+                    map(&curve.add(&pt_p, &pt_q).unwrap()),
+                    image.add(&map(&pt_p), &map(&pt_q)).unwrap()
+                );
+            }
+        }
+        let (short, _) = curve.transform(&F::one(), &r, &s, &t).unwrap();
+        let (a1, a2, a3, _, _, _) = short.get_a_invariants();
+        assert_eq!((a1, a2, a3), (&F::zero(), &F::zero(), &F::zero()));
+
+        assert!(curve
+            .transform(&F::zero(), &F::one(), &F::one(), &F::one())
+            .is_err());
+    }
+
+    #[test]
+    fn is_on_curve_short_and_long_paths_agree() {
+        // The change of variables is a bijection of F103², mapping the long Weierstrass curve
+        // (long path) onto its short model (short path)
+        let curve = EllipticCurve::new((
+            F::one(),
+            F::new(2),
+            F::new(3),
+            F::new(4),
+            F::zero(),
+            F::new(5),
+        ))
+        .unwrap();
+        let half = F::new(2).invert();
+        let r = F::new(9).div(&F::new(12)).neg();
+        let t = F::new(3).add(&r).mul(&half).neg();
+        let (short, map) = curve.transform(&F::one(), &r, &half.neg(), &t).unwrap();
+
+        let mut count = 1;
+        for x in 0..103 {
+            for y in 0..103 {
+                let pt = ECPoint::AffinePoint(F::new(x), F::new(y));
+                let on_curve = curve.is_on_curve(&pt);
+                assert_eq!(on_curve, short.is_on_curve(&map(&pt)));
+                count += on_curve as u32;
+            }
+        }
+        assert_eq!(curve.count_points(), Ok(Integer::from(count)));
+    }
+
+    #[test]
+    fn point_accepts_and_rejects() {
+        let curve = curve();
+        assert_eq!(
+            curve.point(F::new(1), F::new(38)),
+            Ok(ECPoint::AffinePoint(F::new(1), F::new(38)))
+        );
+        assert!(curve.point(F::zero(), F::zero()).is_ok());
+        assert_eq!(
+            curve.point(F::new(1), F::new(1)),
+            Err(ErrorKind::InvalidInput("Point is not on the curve"))
+        );
+        // 2 is not the abscissa of any point
+        assert!((0..103).all(|y| curve.point(F::new(2), F::new(y)).is_err()));
+    }
+
+    #[test]
+    fn mul_negative_zero_and_large_scalars() {
+        let curve = curve();
+        // Of order 104
+        let pt = curve.point(F::new(3), F::new(37)).unwrap();
+        let order = Integer::from(104);
+        assert_eq!(
+            curve.mul(&Integer::new(), &pt),
+            Ok(ECPoint::PointAtInfinity)
+        );
+        assert_eq!(curve.mul(&order, &pt), Ok(ECPoint::PointAtInfinity));
+        assert_eq!(
+            curve.mul(&Integer::from(5), &ECPoint::PointAtInfinity),
+            Ok(ECPoint::PointAtInfinity)
+        );
+        assert_eq!(curve.mul(&Integer::from(-1), &pt), Ok(curve.neg_point(&pt)));
+
+        // [-k]P = -[k]P, and [k]P only depends on k mod 104, even for a 300-bit k
+        let big = (Integer::from(1) << 299u32) + 12345u32;
+        assert_eq!(big.significant_bits(), 300);
+        for k in [Integer::from(7), Integer::from(103), big.clone(), -big] {
+            let pt_k = curve.mul(&k, &pt).unwrap();
+            assert_eq!(curve.mul(&(-k.clone()), &pt), Ok(curve.neg_point(&pt_k)));
+            assert_eq!(curve.mul(&k.clone().modulo(&order), &pt), Ok(pt_k));
+        }
+    }
+
+    #[test]
+    fn uncompressed_round_trip() {
+        let curve = curve();
+        for _ in 0..8 {
+            let pt = curve.random_point().unwrap();
+            let bytes = curve.to_uncompressed(&pt);
+            assert_eq!(bytes.len(), 17);
+            assert_eq!(bytes[0], 0x04);
+            assert_eq!(curve.from_uncompressed(&bytes), Ok(pt));
+        }
+        let inf = ECPoint::PointAtInfinity;
+        let bytes = curve.to_uncompressed(&inf);
+        assert_eq!(bytes, [0; 17]);
+        assert_eq!(curve.from_uncompressed(&bytes), Ok(inf));
+
+        // (1, 1) is not on the curve
+        let mut bytes = vec![0x04];
+        bytes.extend_from_slice(&F::one().to_bytes());
+        bytes.extend_from_slice(&F::one().to_bytes());
+        assert_eq!(
+            curve.from_uncompressed(&bytes),
+            Err(ErrorKind::InvalidInput("Point is not on the curve"))
+        );
+        // Wrong flag, length, and non-canonical coordinate
+        bytes[0] = 0x02;
+        assert!(curve.from_uncompressed(&bytes).is_err());
+        assert!(curve.from_uncompressed(&bytes[..16]).is_err());
+        let mut bytes = curve.to_uncompressed(&curve.point(F::new(3), F::new(37)).unwrap());
+        bytes[8] += 103;
+        assert!(curve.from_uncompressed(&bytes).is_err());
+    }
+
+    #[test]
+    fn mul_window_matches_mul() {
+        let curve = curve();
+        // Of order 104
+        let pt = curve.point(F::new(3), F::new(37)).unwrap();
+        let big = Integer::u_pow_u(3, 161).complete();
+        let scalars =
+            (-120..=120)
+                .map(Integer::from)
+                .chain([big.clone(), -big, Integer::from(u64::MAX)]);
+        for k in scalars {
+            let expected = curve.mul(&k, &pt).unwrap();
+            for w in 2..=8 {
+                assert_eq!(
+                    curve.mul_window(&k, &pt, w),
+                    Ok(expected.clone()),
+                    "k = {k}, w = {w}"
+                );
+            }
+        }
+        assert!(curve.mul_window(&Integer::from(5), &pt, 1).is_err());
+        assert!(curve.mul_window(&Integer::from(5), &pt, 9).is_err());
+    }
+
+    #[test]
+    fn mul_window_addition_count() {
+        // P-256 over a field counting the divisions (two per addition) and the inversions (one
+        // per doubling)
+        type K = Counted<Fq<P256>>;
+        let hex = |s: &str| Counted(Fq::new(Integer::from_str_radix(s, 16).unwrap()));
+        let curve = EllipticCurve::new((
+            K::zero(),
+            K::zero(),
+            K::zero(),
+            K::one().zmul(-3),
+            K::zero(),
+            hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"),
+        ))
+        .unwrap();
+        let pt_g = curve
+            .point(
+                hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+                hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+            )
+            .unwrap();
+
+        for k in [
+            Integer::u_pow_u(3, 161).complete(),
+            Integer::u_pow_u(7, 91).complete(),
+        ] {
+            assert_eq!(k.significant_bits(), 256);
+            take_op_counts();
+            let expected = curve.mul(&k, &pt_g).unwrap();
+            let (divisions, doublings) = take_op_counts();
+            let double_and_add = divisions / 2;
+            let res = curve.mul_window(&k, &pt_g, 5).unwrap();
+            let (divisions, window_doublings) = take_op_counts();
+            let window = divisions / 2;
+            assert_eq!(res, expected);
+
+            // The first doubling and addition of the loops act on the zero point and are free
+            let digits = to_wnaf(&k, 5);
+            let non_zero = digits.iter().filter(|d| **d != 0).count() as u64;
+            assert_eq!(double_and_add, u64::from(k.count_ones().unwrap()) - 1);
+            assert_eq!(doublings, 255);
+            assert_eq!(window, 7 + non_zero - 1);
+            assert_eq!(window_doublings, 1 + digits.len() as u64 - 1);
+            assert!(double_and_add > 2 * window);
+        }
+    }
+
+    #[test]
+    fn from_params_custom_curve() {
+        use alloc::string::ToString;
+
+        // y² = x³ + x over F103, (49, 81) = [8](3, 37) is of order 13
+        let params = |prime: &str, a6: &str, generator: (&str, &str)| CurveParams {
+            base_prime: prime.to_string(),
+            coefficients: ["0", "0", "0", "1", a6].map(|c| c.to_string()),
+            group_order: "13".to_string(),
+            cofactor: "8".to_string(),
+            embedding_degree: 2,
+            generator: (generator.0.to_string(), generator.1.to_string()),
+        };
+
+        let (curve, generator) =
+            EllipticCurve::<F>::from_params(&params("0x67", "0", ("49", "81"))).unwrap();
+        assert_eq!(curve, self::curve());
+        assert_eq!(curve.order(), Some(&Integer::from(13)));
+        assert_eq!(curve.cofactor(), Some(&Integer::from(8)));
+        assert_eq!(generator, ECPoint::AffinePoint(F::new(49), F::new(81)));
+
+        // Over an extension of the base field, coordinates in the prime subfield
+        let (_, generator) =
+            EllipticCurve::<K>::from_params(&params("103", "0", ("49", "-22"))).unwrap();
+        assert_eq!(
+            generator,
+            ECPoint::AffinePoint(K::new(49, 0), K::new(81, 0))
+        );
+
+        // Wrong base prime, off-curve or out-of-subgroup generator
+        for bad in [
+            params("107", "0", ("49", "81")),
+            params("103", "0", ("49", "82")),
+            params("103", "0", ("3", "37")),
+            params("103", "x", ("49", "81")),
+        ] {
+            assert!(EllipticCurve::<F>::from_params(&bad).is_err());
+        }
+    }
+
+    #[test]
+    fn clear_cofactor_g2_lands_in_subgroup() {
+        // E(F103²) = (Z/104)², the cofactor of E[13] is 64. With ψ = π, h(ψ) = ±32 + 32ψ kills
+        // the 2-part (Z/8)² like [64] does, since both coefficients are multiples of 8
+        let curve = curve_k();
+        let r = Integer::from(13);
+        let frobenius = |pt: &ECPoint<K>| curve.frobenius(pt);
+        for _ in 0..8 {
+            let pt = curve.random_point().unwrap();
+            assert_eq!(
+                curve.clear_cofactor_g2(&pt, frobenius, &[Integer::from(64)]),
+                curve.mul(&Integer::from(64), &pt)
+            );
+            for c0 in [32, -32] {
+                let coefficients = [Integer::from(c0), Integer::from(32)];
+                let res = curve
+                    .clear_cofactor_g2(&pt, frobenius, &coefficients)
+                    .unwrap();
+                assert_eq!(curve.mul(&r, &res), Ok(ECPoint::PointAtInfinity));
+                let expected = curve.add(
+                    &curve.mul(&coefficients[0], &pt).unwrap(),
+                    &curve.mul(&coefficients[1], &curve.frobenius(&pt)).unwrap(),
+                );
+                assert_eq!(Ok(res), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn frobenius_satisfies_charpoly() {
+        let curve = curve_k();
+        // X² - tX + q with t = 0, q = 103 for the supersingular curve y² = x³ + x over F103
+        let (t, q) = self::curve().frobenius_charpoly().unwrap();
+        assert_eq!((&t, &q), (&Integer::new(), &Integer::from(103)));
+
+        for _ in 0..8 {
+            let pt = curve.random_point().unwrap();
+            let pi = curve.frobenius(&pt);
+            let pi2 = curve.frobenius(&pi);
+            assert!(curve.is_on_curve(&pi));
+            // π² - [t]π + [q] = 0
+            let res = curve.sub(&pi2, &curve.mul(&t, &pi).unwrap()).unwrap();
+            let res = curve.add(&res, &curve.mul(&q, &pt).unwrap()).unwrap();
+            assert_eq!(res, ECPoint::PointAtInfinity);
+            // π^k is the identity on E(Fq^k), k = 2
+            assert_eq!(pi2, pt);
+        }
+
+        let (pt_p, pt_q) = g1_g2_points();
+        assert_eq!(curve.frobenius(&pt_p), pt_p);
+        assert_eq!(curve.frobenius(&pt_q), curve.mul(&q, &pt_q).unwrap());
+        assert_eq!(
+            curve.frobenius(&ECPoint::PointAtInfinity),
+            ECPoint::PointAtInfinity
+        );
+    }
+
+    #[test]
+    fn is_in_subgroup_cases() {
+        let curve = curve();
+        // (49, 81) = [8](3, 37) is of order 13, (3, 37) of order 104, (0, 0) of order 2
+        let member = |x, y| curve.is_in_subgroup(&curve.point(F::new(x), F::new(y)).unwrap());
+        assert_eq!(member(49, 81), Ok(true));
+        assert_eq!(member(3, 37), Ok(false));
+        assert_eq!(member(0, 0), Ok(false));
+        assert_eq!(curve.is_in_subgroup(&ECPoint::PointAtInfinity), Ok(true));
+
+        let unknown = curve_k();
+        assert!(unknown.is_in_subgroup(&ECPoint::PointAtInfinity).is_err());
+    }
+
+    #[test]
+    fn neg_point_of_infinity_and_two_torsion() {
+        let curve = curve();
+        let inf = ECPoint::PointAtInfinity;
+        assert_eq!(curve.neg_point(&inf), inf);
+
+        // (0, 0) and (±i, 0) are the points of order 2 of y² = x³ + x
+        let pt = curve.point(F::zero(), F::zero()).unwrap();
+        assert_eq!(curve.neg_point(&pt), pt);
+        let curve_k = curve_k();
+        for x in [K::new(0, 1), K::new(0, -1)] {
+            let pt = curve_k.point(x, K::zero()).unwrap();
+            assert_eq!(curve_k.neg_point(&pt), pt);
+            assert_eq!(curve_k.add(&pt, &pt), Ok(ECPoint::PointAtInfinity));
+        }
+
+        // Any other point differs from its inverse
+        let pt = curve.point(F::new(3), F::new(37)).unwrap();
+        assert_ne!(curve.neg_point(&pt), pt);
+        assert_eq!(curve.neg_point(&curve.neg_point(&pt)), pt);
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn hash_to_curve_is_on_curve_and_deterministic() {
+        let curve = curve();
+        for m in 0..32u8 {
+            let pt = curve.hash_to_curve(&[m, 1, 2], b"DST").unwrap();
+            assert!(curve.is_on_curve(&pt));
+            assert_ne!(pt, ECPoint::PointAtInfinity);
+            assert_eq!(pt, curve.hash_to_curve(&[m, 1, 2], b"DST").unwrap());
+        }
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn hash_to_curve_clears_cofactor() {
+        // With the cofactor 8 set, the points are in the order 13 subgroup
+        let curve = curve();
+        let r = Integer::from(13);
+        for m in 0..32u8 {
+            let pt = curve.hash_to_curve(&[m], b"DST").unwrap();
+            assert_eq!(curve.mul(&r, &pt), Ok(ECPoint::PointAtInfinity));
+            assert!(curve.is_in_subgroup(&pt).unwrap());
+        }
+
+        // Without it, the points are only on the curve, and most are not killed by 13
+        let curve = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::one(),
+            F::zero(),
+            F::zero(),
+        ))
+        .unwrap();
+        let outside = (0..32u8)
+            .map(|m| curve.hash_to_curve(&[m], b"DST").unwrap())
+            .filter(|pt| curve.mul(&r, pt) != Ok(ECPoint::PointAtInfinity))
+            .count();
+        assert!(outside > 0);
+
+        // Same with the SSWU map on y² = x³ + 5x + 4 over F103, which also has 104 = 8 × 13 points
+        let sswu_curve = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::new(5),
+            F::zero(),
+            F::new(4),
+        ))
+        .unwrap()
+        .with_sswu_z()
+        .unwrap();
+        let outside = (0..32u8)
+            .map(|m| sswu_curve.hash_to_curve_sswu(&[m], b"DST").unwrap())
+            .filter(|pt| sswu_curve.mul(&r, pt) != Ok(ECPoint::PointAtInfinity))
+            .count();
+        assert!(outside > 0);
+
+        let sswu_curve = sswu_curve
+            .with_order(r.clone())
+            .with_cofactor(Integer::from(8));
+        for m in 0..32u8 {
+            let pt = sswu_curve.hash_to_curve_sswu(&[m], b"DST").unwrap();
+            assert!(sswu_curve.is_in_subgroup(&pt).unwrap());
+        }
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn hash_to_curve_long_dst() {
+        let curve = curve();
+        let dst = [7u8; 300];
+        let short = short_dst::<Sha256>(&dst);
+        assert_eq!(short.len(), 32);
+        assert_eq!(
+            curve.hash_to_curve(b"msg", &dst).unwrap(),
+            curve.hash_to_curve(b"msg", &short).unwrap()
+        );
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn hash_to_curve_gives_up() {
+        // Every point of y² = x³ + x is killed by its group order 104
+        let curve = curve().with_cofactor(Integer::from(104));
+        assert!(curve.hash_to_curve(b"msg", b"DST").is_err());
+    }
+
+    #[test]
+    fn curve_equality() {
+        // 106 = 3 mod 103, the coefficients are compared as field elements
+        let unreduced = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::one(),
+            F::zero(),
+            Fp(106),
+        ))
+        .unwrap();
+        let reduced = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::one(),
+            F::zero(),
+            F::new(3),
+        ))
+        .unwrap();
+        assert_eq!(unreduced, reduced);
+        // The order and cofactor are not part of the curve
+        assert_eq!(curve(), curve().with_order(Integer::from(104)));
+        assert_eq!(curve(), curve().with_cofactor(Integer::from(1)));
+        assert_ne!(curve(), reduced);
+    }
+
+    #[test]
+    fn torsion_basis_generates_r_torsion() {
+        // E(K) ≅ Z/104 × Z/104 contains the full 13-torsion
+        let curve = curve_k();
+        let r = Integer::from(13);
+        let (pt_p, pt_q) = curve.torsion_basis(&r, &Integer::from(104 * 104)).unwrap();
+        for pt in [&pt_p, &pt_q] {
+            assert_ne!(*pt, ECPoint::PointAtInfinity);
+            assert_eq!(curve.mul(&r, pt), Ok(ECPoint::PointAtInfinity));
+        }
+
+        // The Weil pairing is a primitive 13th root of unity
+        let e = weil_pairing(&curve, pt_p.clone(), pt_q.clone(), r.clone()).unwrap();
+        assert!(!e.is_one());
+        assert!(e.pow(&r).is_one());
+
+        // The 169 combinations [a]P + [b]Q are distinct
+        #[cfg(feature = "std")]
+        {
+            let mut torsion = std::collections::HashSet::new();
+            for a in 0..13 {
+                let pt_a = curve.mul(&Integer::from(a), &pt_p).unwrap();
+                for b in 0..13 {
+                    let pt_b = curve.mul(&Integer::from(b), &pt_q).unwrap();
+                    torsion.insert(curve.add(&pt_a, &pt_b).unwrap());
+                }
+            }
+            assert_eq!(torsion.len(), 169);
+        }
+    }
+
+    #[test]
+    fn torsion_basis_wrong_group_order() {
+        // The curve has 104 points, so with 13 as its order most random points are never
+        // killed by a power of 13
+        assert!(curve()
+            .torsion_basis(&Integer::from(13), &Integer::from(13))
+            .is_none());
+    }
+
+    #[test]
+    fn frobenius_eigenvalues() {
+        let curve = curve();
+        let (one, q) = curve.frobenius_eigenvalues_mod(&Integer::from(13)).unwrap();
+        assert_eq!((one, q), (Integer::from(1), Integer::from(12)));
+        for r in [1, 0, -13] {
+            assert!(curve.frobenius_eigenvalues_mod(&Integer::from(r)).is_err());
+        }
+    }
+
+    #[test]
+    fn curve_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<EllipticCurve<F>>();
+    }
+
+    #[test]
+    fn sswu_map_is_on_curve_with_sign_of_u() {
+        // y² = x³ + 2x + 3 over F103
+        let sswu_curve = EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::new(2),
+            F::zero(),
+            F::new(3),
+        ))
+        .unwrap();
+        assert!(sswu_curve.sswu_map(&F::new(5)).is_err());
+        let sswu_curve = sswu_curve.with_sswu_z().unwrap();
+        let z = sswu_curve.sswu_z().unwrap();
+        assert_ne!(z.clone().sqrt().square(), *z);
+        for u in 0..103 {
+            let u = F::new(u);
+            let pt = sswu_curve.sswu_map(&u).unwrap();
+            assert!(sswu_curve.is_on_curve(&pt));
+            let y = pt.y().unwrap();
+            assert!(*y == F::zero() || sgn0(y) == sgn0(&u));
+        }
+
+        #[cfg(feature = "hash")]
+        {
+            let pt = sswu_curve.hash_to_curve_sswu(b"msg", b"DST").unwrap();
+            assert!(sswu_curve.is_on_curve(&pt));
+            assert_eq!(
+                sswu_curve.hash_to_curve_sswu(b"msg", b"DST"),
+                Ok(pt.clone())
+            );
+            assert_ne!(sswu_curve.hash_to_curve_sswu(b"msg", b"DST2"), Ok(pt));
+        }
+
+        // y² = x³ + x, B = 0
+        assert!(curve().with_sswu_z().is_err());
+        assert!(curve().sswu_map(&F::new(5)).is_err());
+    }
+
+    // RFC 9380, appendix J.1.1, P256_XMD:SHA-256_SSWU_RO_ with msg = ""
+    #[cfg(feature = "hash")]
+    #[test]
+    fn hash_to_curve_p256_vector() {
+        type K = Fq<P256>;
+        let hex = |s: &str| K::new(Integer::from_str_radix(s, 16).unwrap());
+        let curve = EllipticCurve::new((
+            K::zero(),
+            K::zero(),
+            K::zero(),
+            K::one().zmul(-3),
+            K::zero(),
+            hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"),
+        ))
+        .unwrap()
+        .with_sswu_z()
+        .unwrap();
+        let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+
+        let u: Vec<K> = hash_to_field(b"", dst, 2).unwrap();
+        assert_eq!(
+            u,
+            [
+                hex("ad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009"),
+                hex("8c0f1d43204bd6f6ea70ae8013070a1518b43873bcd850aafa0a9e220e2eea5a"),
+            ]
+        );
+
+        assert_eq!(curve.sswu_z(), Some(&K::one().zmul(-10)));
+        assert_eq!(
+            curve.hash_to_curve_sswu(b"", dst).unwrap(),
+            ECPoint::AffinePoint(
+                hex("2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4"),
+                hex("8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415"),
+            )
+        );
+    }
 }
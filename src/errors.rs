@@ -1,4 +1,54 @@
+use core::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum ErrorKind {
     InvalidInput(&'static str),
     InvalidPoint,
+    NonResidue,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidInput(msg) => write!(f, "invalid input: {}", msg),
+            ErrorKind::InvalidPoint => write!(f, "invalid point"),
+            ErrorKind::NonResidue => write!(f, "element has no such root"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ErrorKind {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ErrorKind::InvalidInput("empty message").to_string(),
+            "invalid input: empty message"
+        );
+        assert_eq!(ErrorKind::InvalidPoint.to_string(), "invalid point");
+        assert_eq!(
+            ErrorKind::NonResidue.to_string(),
+            "element has no such root"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn boxed_as_std_error() {
+        use alloc::boxed::Box;
+
+        let err: Box<dyn std::error::Error> = Box::new(ErrorKind::InvalidPoint);
+        assert_eq!(err.to_string(), "invalid point");
+        assert!(err.source().is_none());
+        assert_eq!(
+            err.downcast_ref::<ErrorKind>(),
+            Some(&ErrorKind::InvalidPoint)
+        );
+    }
 }
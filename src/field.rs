@@ -1,4 +1,14 @@
-use rug::Integer;
+use alloc::vec::Vec;
+use rug::{ops::Pow, Complete, Integer};
+
+use crate::errors::ErrorKind;
+
+// Number of random elements tried by nth_root to find a non-residue
+const NON_RESIDUE_ATTEMPTS: usize = 128;
+
+// 2-adic valuation of q - 1 from which square_root uses Cipolla's algorithm
+const CIPOLLA_VALUATION: u32 = 8;
+
 /// Generic finite field operations
 pub trait Field {
     /// Neutral element for addition
@@ -7,45 +17,573 @@ pub trait Field {
     /// Neutral element for multiplication
     fn one() -> Self;
 
+    /// Whether the element is the neutral element for multiplication
+    fn is_one(&self) -> bool
+    where
+        Self: PartialEq + Sized,
+    {
+        *self == Self::one()
+    }
+
+    /// Constant-time equality
+    /// Implementations compare canonical (reduced) representations without exiting early
+    /// on the first difference. Values backed by `rug` integers may still leak their size
+    /// through allocation
+    fn ct_eq(&self, y: &Self) -> bool;
+
+    /// Constant-time equality as a `subtle::Choice`, to combine with other `subtle` primitives
+    // Implementations with a branch-free comparison should override it rather than go through
+    // the bool of ct_eq
+    #[cfg(feature = "subtle")]
+    fn ct_eq_choice(&self, y: &Self) -> subtle::Choice {
+        subtle::Choice::from(self.ct_eq(y) as u8)
+    }
+
     /// Addition
     fn add(self, y: &Self) -> Self;
 
     /// Multiplication
     fn mul(self, y: &Self) -> Self;
 
-    /// Multiplication by an integer
+    /// Multiplication by an integer, which may be negative
     fn zmul(self, y: i64) -> Self;
 
-    /// Power
+    /// Power, the exponent may be negative
     fn pow(self, y: &Integer) -> Self;
 
-    /// Int power
+    /// Power by an exponent given by its big endian bits
+    // Lets callers decompose a fixed exponent once and reuse it for many elements
+    fn pow_bits(&self, bits: &[bool]) -> Self
+    where
+        Self: Sized,
+    {
+        let mut y = Self::one();
+        for bit in bits {
+            y = y.square();
+            if *bit {
+                y = y.mul(self);
+            }
+        }
+        y
+    }
+
+    /// Int power, x^(-y) being the inverse of x^y
     fn zpow(self, y: i64) -> Self;
 
     /// Division
     fn div(self, y: &Self) -> Self;
 
+    /// Division, None if y is zero
+    fn checked_div(&self, y: &Self) -> Option<Self>
+    where
+        Self: Clone + PartialEq + Sized,
+    {
+        if *y == Self::zero() {
+            return None;
+        }
+        Some(self.clone().div(y))
+    }
+
+    /// Division, y must not be zero
+    // Meant for hot loops where y is already known to be non-zero, e.g. after the support checks
+    // of the Miller loops. Defaults to div, implementations may skip their own zero handling
+    fn div_unchecked(self, y: &Self) -> Self
+    where
+        Self: Sized,
+    {
+        self.div(y)
+    }
+
+    /// Doubling
+    fn double(self) -> Self
+    where
+        Self: Clone + Sized,
+    {
+        let y = self.clone();
+        self.add(&y)
+    }
+
     /// Squaring
     fn square(self) -> Self;
 
     /// Square root
     fn sqrt(self) -> Self;
 
+    /// Both square roots (r, -r), the smaller one first
+    /// Fails with `NonResidue` if the element is not a square
+    // The ordering relies on PartialOrd comparing canonical representatives
+    fn sqrt_both(&self) -> Result<(Self, Self), ErrorKind>
+    where
+        Self: Clone + PartialOrd + Sized,
+    {
+        let r = self.clone().sqrt();
+        if r.clone().square() != *self {
+            return Err(ErrorKind::NonResidue);
+        }
+        let r_neg = r.clone().neg();
+        if r_neg < r {
+            Ok((r_neg, r))
+        } else {
+            Ok((r, r_neg))
+        }
+    }
+
+    /// N-th root, y such that y^n = x
+    /// Fails with `NonResidue` if the element is not an n-th power
+    // Adleman-Manders-Miller in the field of order Self::order(), only practical when the prime
+    // factors of n dividing the order minus one are small
+    fn nth_root(&self, n: u32) -> Result<Self, ErrorKind>
+    where
+        Self: Clone + PartialEq + Sized,
+    {
+        nth_root(self, n, &Self::order())
+    }
+
+    /// Frobenius x^q, with q the base field order
+    // Implementations with a cheaper Frobenius, e.g. conjugation in a quadratic extension,
+    // should override it
+    fn frobenius(&self) -> Self
+    where
+        Self: Clone + Sized,
+    {
+        self.clone().pow(&Self::base_order())
+    }
+
+    /// Power x^(q^i), with q the base field order
+    // Computed as i applications of frobenius, which is much cheaper than pow when the field
+    // overrides it
+    fn pow_q_power(&self, i: u32) -> Self
+    where
+        Self: Clone + Sized,
+    {
+        let mut y = self.clone();
+        for _ in 0..i {
+            y = y.frobenius();
+        }
+        y
+    }
+
     /// Multiplicative inverse
     fn invert(self) -> Self;
 
     /// Additive inverse
     fn neg(self) -> Self;
 
-    /// Degree of the extension
-    fn degree() -> Self;
+    /// Degree of the extension over the base field
+    fn degree() -> u32;
 
-    /// Field order
-    fn order() -> Self;
+    /// Field order, q^N for a degree N extension of the base field Fq
+    fn order() -> Integer
+    where
+        Self: Sized,
+    {
+        Self::base_order().pow(Self::degree())
+    }
 
     /// Base field order
     fn base_order() -> Integer;
 
+    /// Field characteristic, p for any extension of the prime field Fp
+    /// Unlike the order, it does not depend on the extension degree
+    fn characteristic() -> Integer;
+
     /// Random field point
     fn random_element() -> Self;
 }
+
+/// Fixed width byte encoding of field elements
+pub trait FieldBytes: Field + Sized {
+    /// Size of an encoded element in bytes
+    fn byte_len() -> usize;
+
+    /// Big endian encoding, of byte_len bytes
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Decoding, None unless the input is the canonical encoding of an element
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Sign of a field element, sgn0 of RFC 9380, section 4.1
+/// Returns whether the last byte of its encoding is odd, i.e. the parity of the element of a
+/// prime field
+// /!\ For extension fields this is the parity of the last encoded coordinate, which only matches
+// the RFC when the encoding is chosen accordingly
+pub fn sgn0<F: FieldBytes>(x: &F) -> bool {
+    x.to_bytes().last().is_some_and(|b| b & 1 == 1)
+}
+
+// N-th root, y such that y^n = x in the field with `order` elements (Adleman-Manders-Miller)
+// Fails with NonResidue if x is not an n-th power
+// The root is taken one prime factor r of n at a time. When r does not divide q - 1, x^(1/r mod q - 1)
+// is the only root. Otherwise discrete logarithms are computed in the subgroup of order r by
+// exhaustive search, so this is only practical when the prime factors of n dividing q - 1 are small
+fn nth_root<F: Field + Clone + PartialEq>(x: &F, n: u32, order: &Integer) -> Result<F, ErrorKind> {
+    if n == 0 {
+        return Err(ErrorKind::InvalidInput("Root degree must not be zero"));
+    }
+    if *x == F::zero() {
+        return Ok(F::zero());
+    }
+
+    // Trial division of n
+    let mut root = x.clone();
+    let mut m = n;
+    let mut r = 2;
+    while m > 1 {
+        if r > m / r {
+            r = m;
+        }
+        while m.is_multiple_of(r) {
+            root = prime_root(&root, r, order)?;
+            m /= r;
+        }
+        r += 1;
+    }
+    Ok(root)
+}
+
+/// Square root
+/// Returns y such that y² = x in the field with `order` elements, for an odd order q
+/// Fails with `NonResidue` if x is not a square
+// Meant for Field::sqrt implementations. For q = 3 mod 4, y = x^((q + 1)/4). Otherwise, with
+// q - 1 = 2^s t, Tonelli-Shanks (Field::nth_root) takes O(s²) multiplications, so Cipolla's algorithm
+// is used instead when s is large
+pub fn square_root<F: Field + Clone + PartialEq>(x: &F, order: &Integer) -> Result<F, ErrorKind> {
+    if order.is_even() {
+        return Err(ErrorKind::InvalidInput("Field order must be odd"));
+    }
+    if *x == F::zero() {
+        return Ok(F::zero());
+    }
+
+    let q_m_1 = (order - 1u32).complete();
+    if !x.clone().pow(&(&q_m_1 >> 1u32).complete()).is_one() {
+        return Err(ErrorKind::NonResidue);
+    }
+
+    if order.mod_u(4) == 3 {
+        return Ok(x.clone().pow(&((order + 1u32).complete() >> 2u32)));
+    }
+    if q_m_1.find_one(0).unwrap_or(0) < CIPOLLA_VALUATION {
+        return nth_root(x, 2, order);
+    }
+    cipolla(x, order)
+}
+
+// Cipolla's algorithm, x must be a non-zero square
+// With a such that w² = a² - x is not a square, y = (a + w)^((q + 1)/2) in F[w]
+fn cipolla<F: Field + Clone + PartialEq>(x: &F, order: &Integer) -> Result<F, ErrorKind> {
+    let euler = (order - 1u32).complete() >> 1u32;
+    let (a, w2) = (0..NON_RESIDUE_ATTEMPTS)
+        .map(|_| {
+            let a = F::random_element();
+            let w2 = a.clone().square().add(&x.clone().neg());
+            (a, w2)
+        })
+        .find(|(_, w2)| *w2 != F::zero() && !w2.clone().pow(&euler).is_one())
+        .ok_or(ErrorKind::InvalidInput("Could not find a non-residue"))?;
+
+    // (u0 + u1 w)(v0 + v1 w) = u0 v0 + u1 v1 w² + (u0 v1 + u1 v0) w
+    let mul = |(u0, u1): &(F, F), (v0, v1): &(F, F)| {
+        (
+            u0.clone().mul(v0).add(&u1.clone().mul(v1).mul(&w2)),
+            u0.clone().mul(v1).add(&u1.clone().mul(v0)),
+        )
+    };
+
+    let e = (order + 1u32).complete() >> 1u32;
+    let base = (a, F::one());
+    let mut y = (F::one(), F::zero());
+    for i in (0..e.significant_bits()).rev() {
+        y = mul(&y, &y);
+        if e.get_bit(i) {
+            y = mul(&y, &base);
+        }
+    }
+    Ok(y.0)
+}
+
+// r-th root for a prime r
+fn prime_root<F: Field + Clone + PartialEq>(
+    x: &F,
+    r: u32,
+    order: &Integer,
+) -> Result<F, ErrorKind> {
+    let q_m_1 = (order - 1u32).complete();
+    let r_int = Integer::from(r);
+
+    // r does not divide q - 1: x -> x^r is a bijection
+    if !q_m_1.is_divisible_u(r) {
+        let e = r_int
+            .invert(&q_m_1)
+            .map_err(|_| ErrorKind::InvalidInput("Invalid field order"))?;
+        return Ok(x.clone().pow(&e));
+    }
+
+    // Euler's criterion
+    if !x.clone().pow(&(&q_m_1 / r).complete()).is_one() {
+        return Err(ErrorKind::NonResidue);
+    }
+
+    // q - 1 = r^t s with s prime to r
+    let mut s = q_m_1.clone();
+    let mut t = 0;
+    while s.is_divisible_u(r) {
+        s /= r;
+        t += 1;
+    }
+
+    // Non r-th residue
+    let rho = (0..NON_RESIDUE_ATTEMPTS)
+        .map(|_| F::random_element())
+        .find(|rho| *rho != F::zero() && !rho.clone().pow(&(&q_m_1 / r).complete()).is_one())
+        .ok_or(ErrorKind::InvalidInput("Could not find a non-residue"))?;
+
+    // r alpha = 1 mod s
+    let alpha = if s == 1 {
+        Integer::new()
+    } else {
+        r_int
+            .clone()
+            .invert(&s)
+            .map_err(|_| ErrorKind::InvalidInput("Invalid field order"))?
+    };
+
+    // b lies in the subgroup of order r^t, generated by c
+    let mut b = x.clone().pow(&(Integer::from(&r_int * &alpha) - 1u32));
+    let mut c = rho.pow(&s);
+    let mut h = F::one();
+    // Generator of the subgroup of order r
+    let g = c.clone().pow(&Integer::u_pow_u(r, t - 1).complete());
+
+    for i in 1..t {
+        let d = b.clone().pow(&Integer::u_pow_u(r, t - 1 - i).complete());
+        // d = g^k, c^j with j = -k cancels the corresponding part of b
+        let mut k = 0;
+        let mut gk = F::one();
+        while gk != d {
+            gk = gk.mul(&g);
+            k += 1;
+            if k == r {
+                return Err(ErrorKind::NonResidue);
+            }
+        }
+        let j = Integer::from(r - k) % &r_int;
+        let cr = c.clone().pow(&r_int);
+        b = b.mul(&cr.clone().pow(&j));
+        h = h.mul(&c.pow(&j));
+        c = cr;
+    }
+
+    Ok(x.clone().pow(&alpha).mul(&h))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        params::bn254,
+        test_fields::{Bn254Fp12, Fp, Fp12, Fp2},
+    };
+
+    type F = Fp<103>;
+    type K = Fp2<103>;
+
+    #[test]
+    fn is_one_and_double() {
+        assert!(F::one().is_one());
+        assert!(F::new(104).is_one());
+        assert!(!F::zero().is_one());
+        assert!(!F::new(-1).is_one());
+        assert!(K::one().is_one());
+        assert!(!K::new(1, 1).is_one());
+
+        for v in [0, 1, 51, 52, 60, 102] {
+            let x = F::new(v);
+            assert_eq!(x.clone().double(), x.clone().add(&x));
+            assert_eq!(x.clone().double(), x.zmul(2));
+        }
+        assert_eq!(F::new(60).double(), F::new(17));
+        assert_eq!(K::new(60, 3).double(), K::new(17, 6));
+    }
+
+    #[test]
+    fn ct_eq_equal_and_unequal() {
+        assert!(F::new(5).ct_eq(&F::new(5)));
+        assert!(!F::new(5).ct_eq(&F::new(6)));
+        // Canonical representatives are compared
+        assert!(Fp::<103>(106).ct_eq(&F::new(3)));
+        assert!(!Fp::<103>(106).ct_eq(&F::new(4)));
+        assert!(K::new(1, 2).ct_eq(&K::new(104, -101)));
+        assert!(!K::new(1, 2).ct_eq(&K::new(2, 1)));
+    }
+
+    #[test]
+    fn division_by_zero() {
+        assert_eq!(F::new(6).checked_div(&F::zero()), None);
+        assert_eq!(F::zero().checked_div(&F::zero()), None);
+        assert_eq!(K::new(1, 2).checked_div(&K::zero()), None);
+        assert_eq!(F::new(6).checked_div(&F::new(3)), Some(F::new(2)));
+        assert_eq!(K::new(1, 2).checked_div(&K::new(0, 1)), Some(K::new(2, -1)));
+        assert_eq!(F::new(6).div_unchecked(&F::new(3)), F::new(2));
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn ct_eq_choice() {
+        assert_eq!(F::new(5).ct_eq_choice(&F::new(5)).unwrap_u8(), 1);
+        assert_eq!(F::new(5).ct_eq_choice(&F::new(6)).unwrap_u8(), 0);
+        assert_eq!(Fp::<103>(106).ct_eq_choice(&F::new(3)).unwrap_u8(), 1);
+    }
+
+    #[test]
+    fn sqrt_both_orders_roots() {
+        for v in 1..103 {
+            let x = F::new(v).square();
+            let (r0, r1) = x.sqrt_both().unwrap();
+            assert!(r0 < r1);
+            assert_eq!(r0.clone().neg(), r1);
+            assert_eq!(r0.square(), x);
+        }
+        assert_eq!(F::zero().sqrt_both(), Ok((F::zero(), F::zero())));
+        // 5 is not a square mod 103
+        assert_eq!(F::new(5).sqrt_both(), Err(ErrorKind::NonResidue));
+        assert_eq!(F::new(4).sqrt_both(), Ok((F::new(2), F::new(101))));
+    }
+
+    // Checks square_root on every element of Fp: roots of squares, NonResidue otherwise
+    fn check_square_roots<const P: u64>() {
+        let order = Integer::from(P);
+        assert_eq!(square_root(&Fp::<P>::zero(), &order), Ok(Fp::zero()));
+        let squares: Vec<_> = (1..P as i64).map(|v| Fp::<P>::new(v).square()).collect();
+        for v in 1..P as i64 {
+            let x = Fp::<P>::new(v);
+            match square_root(&x, &order) {
+                Ok(y) => assert_eq!(y.square(), x, "p = {P}, x = {v}"),
+                Err(err) => {
+                    assert_eq!(err, ErrorKind::NonResidue);
+                    assert!(!squares.contains(&x), "p = {P}, x = {v}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn square_root_each_class() {
+        // p = 3 mod 4
+        check_square_roots::<103>();
+        // p = 5 mod 8
+        check_square_roots::<13>();
+        // p = 1 mod 8, 2-adic valuation 4: Tonelli-Shanks
+        check_square_roots::<17>();
+        // 2-adic valuations 8 and 9: Cipolla
+        check_square_roots::<257>();
+        check_square_roots::<7681>();
+
+        for v in [1, 2, 4, 9, 100] {
+            let x = Fp::<257>::new(v);
+            assert_eq!(cipolla(&x, &Integer::from(257)).unwrap().square(), x);
+        }
+        assert!(square_root(&F::one(), &Integer::from(104)).is_err());
+    }
+
+    #[test]
+    fn nth_root_cases() {
+        // 102 = 2 × 3 × 17: x ↦ x⁵ is a bijection, x ↦ x³ is 3 to 1
+        for v in 0..103 {
+            let x = F::new(v);
+            assert_eq!(x.nth_root(5).unwrap().zpow(5), x);
+            let cube = x.clone().zpow(3);
+            assert_eq!(cube.nth_root(3).unwrap().zpow(3), cube);
+            let sixth = x.clone().zpow(6);
+            assert_eq!(sixth.nth_root(6).unwrap().zpow(6), sixth);
+        }
+        // The cubes form a subgroup of index 3, 2 is not one of them
+        assert_ne!(F::new(2).zpow(34), F::one());
+        assert_eq!(F::new(2).nth_root(3), Err(ErrorKind::NonResidue));
+        assert_eq!(F::new(2).nth_root(6), Err(ErrorKind::NonResidue));
+        assert!(F::new(2).nth_root(0).is_err());
+
+        // 103² - 1 = 2⁴ × 3 × 13 × 17, as used for sextic twists
+        let x = K::new(9, 1).zpow(6);
+        assert_eq!(x.nth_root(6).unwrap().zpow(6), x);
+        let x = K::new(9, 1).zpow(13);
+        assert_eq!(x.nth_root(13).unwrap().zpow(13), x);
+        // 9 + i is not a 13-th power
+        assert!(!K::new(9, 1).zpow(10608 / 13).is_one());
+        assert_eq!(K::new(9, 1).nth_root(13), Err(ErrorKind::NonResidue));
+    }
+
+    #[test]
+    fn pow_bits_matches_pow() {
+        let x = K::new(5, 7);
+        assert_eq!(x.pow_bits(&[]), K::one());
+        // 13 = 0b1101, leading zeros do not change the result
+        assert_eq!(
+            x.pow_bits(&[true, true, false, true]),
+            x.clone().pow(&Integer::from(13))
+        );
+        assert_eq!(
+            x.pow_bits(&[false, false, true, true, false, true]),
+            x.clone().pow(&Integer::from(13))
+        );
+        // x^(q² - 1) = 1
+        let e = Integer::from(103 * 103 - 1);
+        let bits: Vec<bool> = (0..e.significant_bits())
+            .rev()
+            .map(|i| e.get_bit(i))
+            .collect();
+        assert!(x.pow_bits(&bits).is_one());
+        assert_eq!(F::zero().pow_bits(&[true]), F::zero());
+    }
+
+    #[test]
+    fn pow_q_power_matches_pow() {
+        let x = K::new(5, 7);
+        assert_eq!(x.pow_q_power(0), x);
+        assert_eq!(x.pow_q_power(1), x.clone().pow(&Integer::from(103)));
+        assert_eq!(x.pow_q_power(1), K::new(5, -7));
+        // x^(q²) = x in a degree 2 extension
+        assert_eq!(x.pow_q_power(2), x);
+        assert_eq!(F::new(5).pow_q_power(3), F::new(5));
+
+        type K12 = Fp12<Bn254Fp12>;
+        let p = bn254::base_prime();
+        let y = K12::random_element();
+        assert_eq!(y.pow_q_power(1), y.clone().pow(&p));
+        assert_eq!(y.pow_q_power(2), y.clone().pow(&p.clone().pow(2)));
+        assert_eq!(y.pow_q_power(12), y);
+    }
+
+    // Only uses the trait: degree and order are available to generic code
+    fn degree_and_order<T: Field>() -> (u32, Integer) {
+        (T::degree(), T::order())
+    }
+
+    #[test]
+    fn degree_and_order_of_fields() {
+        assert_eq!(degree_and_order::<F>(), (1, Integer::from(103)));
+        assert_eq!(degree_and_order::<K>(), (2, Integer::from(103 * 103)));
+        let p = bn254::base_prime();
+        assert_eq!(
+            degree_and_order::<Fp12<Bn254Fp12>>(),
+            (12, p.clone().pow(12))
+        );
+        assert_eq!(K::base_order(), 103);
+    }
+
+    #[test]
+    fn characteristic_of_extensions() {
+        assert_eq!(F::characteristic(), 103);
+        assert_eq!(K::characteristic(), 103);
+        assert_eq!(K::order(), 103 * 103);
+
+        // The characteristic is the base prime whatever the degree, the order is not
+        type K12 = Fp12<Bn254Fp12>;
+        let p = bn254::base_prime();
+        assert_eq!(K12::characteristic(), p);
+        assert_eq!(K12::base_order(), p);
+        assert_ne!(K12::order(), p);
+        assert_eq!(K12::characteristic().pow(K12::degree()), K12::order());
+    }
+}
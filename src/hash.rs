@@ -0,0 +1,241 @@
+use alloc::{vec, vec::Vec};
+use hmac::{Hmac, Mac};
+use rug::{integer::Order, Integer};
+use sha2::Digest;
+
+/// SHA-256, from the sha2 crate
+pub use sha2::Sha256;
+
+use crate::{bigint::to_be_bytes, errors::ErrorKind, field::Field, params::element};
+
+/// SHA-256 digest of `data`
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    <Sha256 as Digest>::digest(data).into()
+}
+
+/// Hash function for expand_message_xmd
+pub trait XmdHash {
+    /// Output size in bytes
+    const OUTPUT_LEN: usize;
+
+    /// Input block size in bytes
+    const BLOCK_LEN: usize;
+
+    /// Digest of `data`
+    fn digest(data: &[u8]) -> Vec<u8>;
+}
+
+impl XmdHash for Sha256 {
+    const OUTPUT_LEN: usize = 32;
+    const BLOCK_LEN: usize = 64;
+
+    fn digest(data: &[u8]) -> Vec<u8> {
+        sha256(data).to_vec()
+    }
+}
+
+/// expand_message_xmd from RFC 9380, section 5.3.1
+/// Returns `len` uniformly random bytes derived from `msg` and the domain separation tag `dst`
+// Tags longer than 255 bytes are replaced with H("H2C-OVERSIZE-DST-" || dst)
+pub fn expand_message_xmd<H: XmdHash>(
+    msg: &[u8],
+    dst: &[u8],
+    len: usize,
+) -> Result<Vec<u8>, ErrorKind> {
+    let ell = len.div_ceil(H::OUTPUT_LEN);
+    if ell > 255 || len > 65535 {
+        return Err(ErrorKind::InvalidInput("Requested length is too large"));
+    }
+
+    // DST' = DST || I2OSP(len(DST), 1)
+    let mut dst_prime = short_dst::<H>(dst);
+    dst_prime.push(dst_prime.len() as u8);
+
+    // b0 = H(Z_pad || msg || I2OSP(len, 2) || I2OSP(0, 1) || DST')
+    let mut msg_prime = vec![0; H::BLOCK_LEN];
+    msg_prime.extend_from_slice(msg);
+    msg_prime.extend_from_slice(&(len as u16).to_be_bytes());
+    msg_prime.push(0);
+    msg_prime.extend_from_slice(&dst_prime);
+    let b_0 = H::digest(&msg_prime);
+
+    // b1 = H(b0 || I2OSP(1, 1) || DST'), bi = H((b0 xor b(i-1)) || I2OSP(i, 1) || DST')
+    let mut out = Vec::with_capacity(ell * H::OUTPUT_LEN);
+    let mut b_i = vec![0; H::OUTPUT_LEN];
+    for i in 1..=ell {
+        let mut data: Vec<u8> = b_0.iter().zip(&b_i).map(|(x, y)| x ^ y).collect();
+        data.push(i as u8);
+        data.extend_from_slice(&dst_prime);
+        b_i = H::digest(&data);
+        out.extend_from_slice(&b_i);
+    }
+    out.truncate(len);
+    Ok(out)
+}
+
+// Domain separation tag of at most 255 bytes, so that its length fits in one byte
+// Longer tags are replaced with H("H2C-OVERSIZE-DST-" || dst), see RFC 9380, section 5.3.3
+pub(crate) fn short_dst<H: XmdHash>(dst: &[u8]) -> Vec<u8> {
+    if dst.len() > 255 {
+        H::digest(&[b"H2C-OVERSIZE-DST-", dst].concat())
+    } else {
+        dst.to_vec()
+    }
+}
+
+/// hash_to_field from RFC 9380, section 5.2, with expand_message_xmd and SHA-256
+/// Returns `count` elements of the prime subfield of F
+pub fn hash_to_field<F: Field>(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<F>, ErrorKind> {
+    hash_to_field_with::<F, Sha256>(msg, dst, count)
+}
+
+/// hash_to_field with expand_message_xmd and the hash function H
+// Each element is reduced from L = ceil((ceil(log2(p)) + 128) / 8) bytes, for a bias of at most
+// 2^-128. Elements of extension fields would need one such reduction per coordinate, which the
+// Field trait cannot build, so only the prime subfield is reached
+pub fn hash_to_field_with<F: Field, H: XmdHash>(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Result<Vec<F>, ErrorKind> {
+    let p = F::characteristic();
+    let l = (p.significant_bits() as usize + 128).div_ceil(8);
+    let bytes = expand_message_xmd::<H>(msg, dst, count * l)?;
+    Ok(bytes
+        .chunks(l)
+        .map(|chunk| element(&Integer::from_digits(chunk, Order::Msf).modulo(&p)))
+        .collect())
+}
+
+// HMAC-SHA-256 (RFC 2104)
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC keys may have any length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// Deterministic nonce from RFC 6979, section 3.2, with HMAC-SHA-256
+/// Returns k in [1, order) derived from the secret key `sk` and the SHA-256 digest of `msg`, so
+/// that signing needs no random number generator
+/// Fails if order is not greater than 1
+pub fn deterministic_scalar(
+    sk: &Integer,
+    msg: &[u8],
+    order: &Integer,
+) -> Result<Integer, ErrorKind> {
+    if *order <= 1 {
+        return Err(ErrorKind::InvalidInput("Order must be greater than 1"));
+    }
+    let qlen = order.significant_bits() as usize;
+    let rlen = qlen.div_ceil(8);
+
+    // bits2int: the leftmost qlen bits of b
+    let bits2int = |b: &[u8]| {
+        let v = Integer::from_digits(b, Order::Msf);
+        match (8 * b.len()).checked_sub(qlen) {
+            Some(shift) => v >> shift as u32,
+            None => v,
+        }
+    };
+    // int2octets: rlen bytes, big endian
+    let int2octets = |v: &Integer| to_be_bytes(v, rlen);
+
+    let x = int2octets(&sk.clone().modulo(order));
+    let h = int2octets(&bits2int(&sha256(msg)).modulo(order));
+
+    let mut v = [1u8; 32];
+    let mut k = [0u8; 32];
+    for sep in [0u8, 1] {
+        k = hmac_sha256(&k, &[&v[..], &[sep], &x, &h].concat());
+        v = hmac_sha256(&k, &v);
+    }
+
+    loop {
+        let mut t = Vec::with_capacity(rlen + 32);
+        while t.len() < rlen {
+            v = hmac_sha256(&k, &v);
+            t.extend_from_slice(&v);
+        }
+        let nonce = bits2int(&t);
+        if nonce >= 1 && nonce < *order {
+            return Ok(nonce);
+        }
+        k = hmac_sha256(&k, &[&v[..], &[0]].concat());
+        v = hmac_sha256(&k, &v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fields::Fp;
+    use alloc::{format, string::String};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // RFC 9380, appendix K.1
+    #[test]
+    fn expand_message_xmd_vectors() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let expand = |msg: &[u8], len| hex(&expand_message_xmd::<Sha256>(msg, dst, len).unwrap());
+        assert_eq!(
+            expand(b"", 0x20),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            expand(b"abc", 0x20),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+        assert_eq!(
+            expand(b"", 0x80),
+            "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe\
+             e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18\
+             eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc\
+             c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"
+        );
+        assert!(expand_message_xmd::<Sha256>(b"", dst, 256 * 32).is_err());
+    }
+
+    #[test]
+    fn hash_to_field_is_deterministic() {
+        let u: Vec<Fp<103>> = hash_to_field(b"abc", b"DST", 3).unwrap();
+        assert_eq!(u.len(), 3);
+        assert_eq!(u, hash_to_field::<Fp<103>>(b"abc", b"DST", 3).unwrap());
+        assert_ne!(u, hash_to_field::<Fp<103>>(b"abc", b"DSU", 3).unwrap());
+    }
+
+    // RFC 6979, appendix A.2.5 (P-256) and A.1.2 (163-bit q), with SHA-256
+    #[test]
+    fn deterministic_scalar_vectors() {
+        let int = |s: &str| Integer::from_str_radix(s, 16).unwrap();
+        let q = int("FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551");
+        let x = int("C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721");
+        assert_eq!(
+            deterministic_scalar(&x, b"sample", &q).unwrap(),
+            int("A6E3C57DD01ABE90086538398355DD4C3B17AA873382B0F24D6129493D8AAD60")
+        );
+
+        let q = int("4000000000000000000020108A2E0CC0D99F8A5EF");
+        let x = int("09A4D6792295A7F730FC3F2B49CBC0F62E862272F");
+        assert_eq!(
+            deterministic_scalar(&x, b"sample", &q).unwrap(),
+            int("23AF4074C90A02B3FE61D286D5C87F425E6BDD81B")
+        );
+    }
+
+    #[test]
+    fn deterministic_scalar_is_deterministic() {
+        let x = Integer::from(123_456_789);
+        let q = Integer::from(1_000_003);
+        let k = deterministic_scalar(&x, b"m1", &q).unwrap();
+        assert_eq!(k, deterministic_scalar(&x, b"m1", &q).unwrap());
+        assert_ne!(k, deterministic_scalar(&x, b"m2", &q).unwrap());
+        for m in 0..200u8 {
+            let k = deterministic_scalar(&x, &[m], &Integer::from(7)).unwrap();
+            assert!((1..7).contains(&k));
+        }
+        assert!(deterministic_scalar(&x, b"m", &Integer::from(1)).is_err());
+    }
+}
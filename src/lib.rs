@@ -1,11 +1,44 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+/// Arbitrary precision integers
+pub mod bigint;
+
 /// Elliptic curve operations
 pub mod elliptic_curve;
 
 /// Finite field operations
 pub mod field;
 
+/// Polynomials
+pub mod poly;
+
 /// Elliptic curve pairings
 pub mod pairings;
 
 /// Errors
 pub mod errors;
+
+/// Hash functions
+#[cfg(feature = "hash")]
+pub mod hash;
+
+/// Curve parameters
+pub mod params;
+
+/// Twists of elliptic curves
+pub mod twist;
+
+/// Twisted Edwards curves
+pub mod edwards;
+
+/// Montgomery curves
+pub mod montgomery;
+
+/// BLS signatures
+#[cfg(feature = "hash")]
+pub mod bls;
+
+#[cfg(test)]
+mod test_fields;
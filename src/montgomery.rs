@@ -0,0 +1,204 @@
+use rug::Integer;
+
+use crate::{
+    elliptic_curve::{ECPoint, EllipticCurve},
+    errors::ErrorKind,
+    field::Field,
+};
+
+// Montgomery curve B v² = u³ + A u² + u
+// Points are ECPoint values with coordinates (u, v)
+#[derive(Clone, Debug, PartialEq)]
+pub struct MontgomeryCurve<F: Field> {
+    a: F,
+    b: F,
+}
+
+impl<F: Field + Clone + PartialEq> MontgomeryCurve<F> {
+    // New Montgomery curve, B must be non-zero and A² ≠ 4
+    pub fn new(a: F, b: F) -> Result<Self, ErrorKind> {
+        if b == F::zero() || a.clone().square() == F::one().zmul(4) {
+            return Err(ErrorKind::InvalidInput(
+                "Montgomery coefficients must satisfy B ≠ 0 and A² ≠ 4",
+            ));
+        }
+        Ok(MontgomeryCurve { a, b })
+    }
+
+    // Get (A, B)
+    pub fn get_coefficients(&self) -> (&F, &F) {
+        (&self.a, &self.b)
+    }
+
+    // Check that point is on the curve
+    pub fn is_on_curve(&self, pt_p: &ECPoint<F>) -> bool {
+        match pt_p {
+            ECPoint::PointAtInfinity => true,
+            ECPoint::AffinePoint(u, v) => {
+                // B v² = u³ + A u² + u
+                self.b.clone().mul(&v.clone().square())
+                    == u.clone()
+                        .zpow(3)
+                        .add(&u.clone().square().mul(&self.a))
+                        .add(u)
+            }
+        }
+    }
+
+    // Returns the u-coordinate of [k]P given the u-coordinate of P, with the Montgomery ladder
+    // The ladder works on projective (U : Z) coordinates and only inverts Z at the end, it does
+    // not depend on B so u may also be the coordinate of a point on the quadratic twist
+    // Returns 0 when [k]P is the zero point
+    // /!\ k must be non-negative, and the ladder is not constant time
+    pub fn x_mul(&self, k: &Integer, u: &F) -> F {
+        // a24 = (A + 2) / 4
+        let a24 = self
+            .a
+            .clone()
+            .add(&F::one().double())
+            .div(&F::one().zmul(4));
+
+        // Invariant: R1 - R0 = P
+        let mut r0 = (F::one(), F::zero());
+        let mut r1 = (u.clone(), F::one());
+        for i in (0..k.significant_bits()).rev() {
+            if k.get_bit(i) {
+                r0 = x_add(&r0, &r1, u);
+                r1 = x_double(&r1, &a24);
+            } else {
+                r1 = x_add(&r0, &r1, u);
+                r0 = x_double(&r0, &a24);
+            }
+        }
+
+        let (u_k, z_k) = r0;
+        if z_k == F::zero() {
+            return F::zero();
+        }
+        u_k.div(&z_k)
+    }
+
+    // Birationally equivalent short Weierstrass curve y² = x³ + a4 x + a6,
+    // with (x, y) = (u / B + A / 3B, v / B)
+    // a4 = (3 - A²) / 3B², a6 = (2A³ - 9A) / 27B³
+    // /!\ The characteristic must not be 2 or 3
+    pub fn to_weierstrass(&self) -> EllipticCurve<F> {
+        let a2 = self.a.clone().square();
+        let b2 = self.b.clone().square();
+        let a4 = F::one()
+            .zmul(3)
+            .add(&a2.clone().neg())
+            .div(&b2.clone().zmul(3));
+        let a6 = a2
+            .double()
+            .add(&F::one().zmul(-9))
+            .mul(&self.a)
+            .div(&b2.mul(&self.b).zmul(27));
+        EllipticCurve::new_long_weierstrass((F::zero(), F::zero(), F::zero(), a4, F::zero(), a6))
+    }
+
+    // Maps P to the curve returned by to_weierstrass, (u, v) -> (u / B + A / 3B, v / B)
+    pub fn to_weierstrass_point(&self, pt_p: &ECPoint<F>) -> ECPoint<F> {
+        match pt_p {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(u, v) => {
+                let x = u
+                    .clone()
+                    .add(&self.a.clone().div(&F::one().zmul(3)))
+                    .div(&self.b);
+                let y = v.clone().div(&self.b);
+                ECPoint::AffinePoint(x, y)
+            }
+        }
+    }
+
+    // Maps a point of the curve returned by to_weierstrass back, (x, y) -> (B x - A / 3, B y)
+    pub fn from_weierstrass_point(&self, pt_p: &ECPoint<F>) -> ECPoint<F> {
+        match pt_p {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(x, y) => {
+                let u = self
+                    .b
+                    .clone()
+                    .mul(x)
+                    .add(&self.a.clone().div(&F::one().zmul(3)).neg());
+                let v = self.b.clone().mul(y);
+                ECPoint::AffinePoint(u, v)
+            }
+        }
+    }
+}
+
+// Differential addition: (U : Z) of P + Q from P, Q and the u-coordinate of P - Q
+fn x_add<F: Field + Clone>(pt_p: &(F, F), pt_q: &(F, F), u_diff: &F) -> (F, F) {
+    let (u_p, z_p) = pt_p;
+    let (u_q, z_q) = pt_q;
+
+    // (UP - ZP)(UQ + ZQ) and (UP + ZP)(UQ - ZQ)
+    let da = u_p
+        .clone()
+        .add(&z_p.clone().neg())
+        .mul(&u_q.clone().add(z_q));
+    let cb = u_p
+        .clone()
+        .add(z_p)
+        .mul(&u_q.clone().add(&z_q.clone().neg()));
+
+    // U = (da + cb)², Z = u (da - cb)²
+    let u = da.clone().add(&cb).square();
+    let z = u_diff.clone().mul(&da.add(&cb.neg()).square());
+    (u, z)
+}
+
+// Doubling: (U : Z) of 2P, with a24 = (A + 2) / 4
+fn x_double<F: Field + Clone>(pt_p: &(F, F), a24: &F) -> (F, F) {
+    let (u_p, z_p) = pt_p;
+
+    // (U + Z)², (U - Z)² and their difference 4 U Z
+    let aa = u_p.clone().add(z_p).square();
+    let bb = u_p.clone().add(&z_p.clone().neg()).square();
+    let e = aa.clone().add(&bb.clone().neg());
+
+    // U = (U + Z)² (U - Z)², Z = 4 U Z ((U - Z)² + a24 4 U Z)
+    let u = aa.mul(&bb);
+    let z = e.clone().mul(&bb.add(&a24.clone().mul(&e)));
+    (u, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fields::Fp;
+
+    type F = Fp<103>;
+
+    // v² = u³ + 3u² + u over F103
+    fn curve() -> MontgomeryCurve<F> {
+        MontgomeryCurve::new(F::new(3), F::one()).unwrap()
+    }
+
+    #[test]
+    fn x_mul_matches_weierstrass_mul() {
+        let curve = curve();
+        let weierstrass = curve.to_weierstrass();
+        let order = weierstrass.count_points().unwrap();
+        let u_of = |pt: &ECPoint<F>| match curve.from_weierstrass_point(pt) {
+            ECPoint::PointAtInfinity => F::zero(),
+            ECPoint::AffinePoint(u, _) => u,
+        };
+
+        for _ in 0..4 {
+            let pt_w = weierstrass.random_point().unwrap();
+            let pt = curve.from_weierstrass_point(&pt_w);
+            assert!(curve.is_on_curve(&pt));
+            assert_eq!(curve.to_weierstrass_point(&pt), pt_w);
+            let u = u_of(&pt_w);
+            for k in (0..40).map(Integer::from).chain([order.clone() - 1u32]) {
+                let pt_k = weierstrass.mul(&k, &pt_w).unwrap();
+                assert_eq!(curve.x_mul(&k, &u), u_of(&pt_k), "k = {k}");
+            }
+            // [#E]P = 0
+            assert_eq!(curve.x_mul(&order, &u), F::zero());
+        }
+    }
+}
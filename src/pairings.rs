@@ -1,47 +1,445 @@
 use rug::{Complete, Integer};
 
-use std::ops::{Div, Sub};
+use alloc::{vec, vec::Vec};
+use core::ops::Mul;
 
 use crate::{
-    elliptic_curve::{ECPoint, EllipticCurve},
+    bigint::BigInteger,
+    elliptic_curve::{to_wnaf, ECPoint, EllipticCurve, LineCoeffs},
     errors::ErrorKind,
     field::Field,
 };
 
+/// Pairing selection for verify_bilinear
+// Only the parameters other than the order and the embedding degree are given here
+#[derive(Clone, Debug, PartialEq)]
+pub enum PairingKind {
+    Weil,
+    Tate,
+    Ate {
+        trace_m_1: Integer,
+    },
+    OptimalAte {
+        loop_count: Integer,
+        family: CurveFamily,
+    },
+}
+
+/// Family of pairing-friendly curves, which gives the Miller function of optimal_ate_pairing
+// BLS curves only need f_{c,Q}, BN curves also need two lines through Frobenius images of Q
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveFamily {
+    Bn,
+    Bls,
+}
+
+// Number of random points tried by tate_pairing to move the poles away from Q
+const POLE_MOVING_ATTEMPTS: usize = 16;
+
+// Largest embedding degree found by embedding_degree
+const MAX_EMBEDDING_DEGREE: u32 = 1 << 16;
+
 trait IntegerExt {
-    fn is_zero(&self) -> bool;
-    fn is_positive(&self) -> bool;
-    fn large_pow(&self, other: &Self) -> Self;
-    fn to_bits(self) -> Vec<bool>;
+    fn bits(&self) -> impl Iterator<Item = bool> + '_;
+    fn to_naf(self) -> Vec<i8>;
+}
+
+impl<I: BigInteger> IntegerExt for I {
+    // Bits of |self|, most significant first, read one at a time without allocating
+    fn bits(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.significant_bits()).rev().map(|i| self.get_bit(i))
+    }
+
+    // Little endian non-adjacent form of |self|: digits in {-1, 0, 1}, no two adjacent non-zero
+    fn to_naf(self) -> Vec<i8> {
+        let mut k = self.abs();
+        let four = I::from_i64(4);
+        let mut naf = vec![];
+        while !k.is_zero() {
+            if k.is_odd() {
+                // 1 if k = 1 mod 4, -1 if k = 3 mod 4
+                let digit = 2 - k.modulo(&four).to_i64().unwrap_or(0) as i8;
+                k = k.sub(&I::from_i64(digit as i64));
+                naf.push(digit);
+            } else {
+                naf.push(0);
+            }
+            k = k.shr(1);
+        }
+        naf
+    }
+}
+
+/// Element of the pairing target group, the order n subgroup of Fq^k*
+// Only the group operations are exposed: adding two pairing values is meaningless
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Gt<F: Field>(F);
+
+impl<F: Field + Clone + PartialEq> Gt<F> {
+    /// Neutral element
+    pub fn one() -> Self {
+        Gt(F::one())
+    }
+
+    /// Whether the element is the neutral element
+    pub fn is_one(&self) -> bool {
+        self.0.is_one()
+    }
+
+    /// Power, e^k
+    pub fn pow(self, k: &Integer) -> Self {
+        Gt(self.0.pow(k))
+    }
+
+    /// Inverse
+    pub fn invert(self) -> Self {
+        Gt(self.0.invert())
+    }
+
+    // Underlying field element, to pin pairing values in tests
+    #[cfg(test)]
+    pub(crate) fn value(&self) -> &F {
+        &self.0
+    }
+}
+
+/// Group operation
+impl<F: Field> Mul<&Gt<F>> for Gt<F> {
+    type Output = Gt<F>;
+
+    fn mul(self, y: &Gt<F>) -> Gt<F> {
+        Gt(self.0.mul(&y.0))
+    }
+}
+
+impl<F: Field> Mul for Gt<F> {
+    type Output = Gt<F>;
+
+    fn mul(self, y: Gt<F>) -> Gt<F> {
+        self * &y
+    }
+}
+
+/// Miller loop output, before the final exponentiation
+// Values such as f_{n,P}(Q) are only defined up to n-th powers: they must go through reduce
+// before being compared, which the Gt wrapper ensures
+#[derive(Clone, Debug)]
+pub struct UnreducedPairing<F: Field>(F);
+
+impl<F: Field + Clone + PartialEq> UnreducedPairing<F> {
+    /// Wraps a Miller loop output
+    pub fn new(f: F) -> Self {
+        UnreducedPairing(f)
+    }
+
+    /// Neutral element
+    pub fn one() -> Self {
+        UnreducedPairing(F::one())
+    }
+
+    /// Final exponentiation, f^((q^k - 1)/n) with n = order and k = embedding degree
+    // Fails if n does not divide q^k - 1
+    pub fn reduce(&self, embedding_degree: u32, order: &Integer) -> Result<Gt<F>, ErrorKind> {
+        let q = F::base_order();
+        Ok(Gt(final_exponentiation(
+            &self.0,
+            &q,
+            embedding_degree,
+            order,
+        )?))
+    }
+}
+
+/// Product of Miller loop outputs
+impl<F: Field> Mul<&UnreducedPairing<F>> for UnreducedPairing<F> {
+    type Output = UnreducedPairing<F>;
+
+    fn mul(self, y: &UnreducedPairing<F>) -> UnreducedPairing<F> {
+        UnreducedPairing(self.0.mul(&y.0))
+    }
+}
+
+impl<F: Field> Mul for UnreducedPairing<F> {
+    type Output = UnreducedPairing<F>;
+
+    fn mul(self, y: UnreducedPairing<F>) -> UnreducedPairing<F> {
+        self * &y
+    }
+}
+
+// Returns the evaluation at R of the vertical line through P
+// The vertical line through the zero point is the constant 1
+fn vertical<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_r: &ECPoint<F>,
+) -> Result<F, ErrorKind> {
+    match pt_p {
+        ECPoint::PointAtInfinity => Ok(F::one()),
+        ECPoint::AffinePoint(_, _) => curve.line(pt_p, &curve.invert(pt_p)?, pt_r),
+    }
+}
+
+// Error returned when the evaluation point is in the support of a Miller function
+const IN_SUPPORT: ErrorKind = ErrorKind::InvalidInput("Q is in the support of the Miller function");
+
+// Fails if a line or a vertical line vanishes at the evaluation point
+fn check_support<F: Field + PartialEq>(ell: &F, vee: &F) -> Result<(), ErrorKind> {
+    if *ell == F::zero() || *vee == F::zero() {
+        return Err(IN_SUPPORT);
+    }
+    Ok(())
+}
+
+/// Embedding degree
+/// Returns the multiplicative order k of q modulo r, i.e. the smallest k such that r | q^k - 1
+// Fails if q and r are not coprime, or if k exceeds MAX_EMBEDDING_DEGREE: such curves are not
+// pairing-friendly anyway
+pub fn embedding_degree<I: BigInteger>(q: &I, r: &I) -> Result<u32, ErrorKind> {
+    let one = I::from_i64(1);
+    if *r <= one {
+        return Err(ErrorKind::InvalidInput("r must be greater than 1"));
+    }
+    // q is invertible mod r exactly when they are coprime
+    if q.invert_mod(r).is_none() {
+        return Err(ErrorKind::InvalidInput("q and r must be coprime"));
+    }
+
+    let q_r = q.modulo(r);
+    let mut t = q_r.clone();
+    for k in 1..=MAX_EMBEDDING_DEGREE {
+        if t == one {
+            return Ok(k);
+        }
+        t = t.mul(&q_r).modulo(r);
+    }
+
+    Err(ErrorKind::InvalidInput("Embedding degree is too large"))
 }
 
-impl IntegerExt for Integer {
-    fn is_zero(&self) -> bool {
-        *self == 0
+/// Pairing parameters validation
+/// Fails unless q and order are coprime and order | q^k - 1, with k the embedding degree
+// k need not be the smallest such integer, see embedding_degree
+pub fn validate_pairing_params<I: BigInteger>(
+    q: &I,
+    order: &I,
+    embedding_degree: u32,
+) -> Result<(), ErrorKind> {
+    let one = I::from_i64(1);
+    if *order <= one {
+        return Err(ErrorKind::InvalidInput("Order must be greater than 1"));
     }
+    if q.invert_mod(order).is_none() {
+        return Err(ErrorKind::InvalidInput("q and order must be coprime"));
+    }
+    if embedding_degree == 0 {
+        return Err(ErrorKind::InvalidInput("Embedding degree must not be zero"));
+    }
+    let q_k = q
+        .pow_mod(&I::from_i64(embedding_degree.into()), order)
+        .ok_or(ErrorKind::InvalidInput("Invalid pairing parameters"))?;
+    if q_k != one {
+        return Err(ErrorKind::InvalidInput(
+            "order does not divide q^k - 1 for this embedding degree",
+        ));
+    }
+    Ok(())
+}
+
+/// Final exponentiation
+/// Returns f^e where e = (q^k - 1)/n with q = base field size, k = embedding degree and n = group order
+// For even k, the exponent is split as (q^(k/2) - 1) * ((q^(k/2) + 1)/n):
+// the easy part is computed first, and the hard part is applied to its (smaller) result
+// Fails if n does not divide q^k - 1, as the exponent would not be an integer, or if f is zero and
+// the exponent is split
+pub fn final_exponentiation<F: Field + Clone + PartialEq, I: BigInteger>(
+    f: &F,
+    base_order: &I,
+    embedding_degree: u32,
+    group_order: &I,
+) -> Result<F, ErrorKind> {
+    let (easy, hard) = final_exponent(base_order, embedding_degree, group_order)?;
+    let f = match easy {
+        // Easy part: f^(q^(k/2) - 1), then hard part: ^((q^(k/2) + 1)/n)
+        Some(half) => easy_part(f, base_order, half)?,
+        None => f.clone(),
+    };
+    Ok(f.pow_bits(&hard.bits().collect::<Vec<_>>()))
+}
 
-    fn is_positive(&self) -> bool {
-        let zero: Self = 0.into();
-        *self > zero
+// Splits the final exponent e = (q^k - 1)/n
+// Returns (Some(k/2), (q^(k/2) + 1)/n) when k is even and n | q^(k/2) + 1, and (None, e)
+// otherwise
+// Fails if n is zero or does not divide q^k - 1
+fn final_exponent<I: BigInteger>(
+    base_order: &I,
+    embedding_degree: u32,
+    group_order: &I,
+) -> Result<(Option<u32>, I), ErrorKind> {
+    let one = I::from_i64(1);
+    if group_order.is_zero() {
+        return Err(ErrorKind::InvalidInput("Group order must not be zero"));
     }
 
-    fn large_pow(&self, _other: &Self) -> Self {
-        unimplemented!()
+    if embedding_degree.is_multiple_of(2) {
+        let hard = base_order.pow(embedding_degree / 2).add(&one);
+
+        if hard.modulo(group_order).is_zero() {
+            return Ok((Some(embedding_degree / 2), hard.div(group_order)));
+        }
     }
 
-    fn to_bits(self) -> Vec<bool> {
-        unimplemented!()
+    let e = base_order.pow(embedding_degree).sub(&one);
+    if !e.modulo(group_order).is_zero() {
+        return Err(ErrorKind::InvalidInput(
+            "order does not divide q^k - 1 for this embedding degree",
+        ));
+    }
+    Ok((None, e.div(group_order)))
+}
+
+// Easy part of the final exponentiation, f^(q^(k/2) - 1) with half = k/2
+// f^(q^(k/2)) goes through the Frobenius when q is the base field order of F
+// Fails if f is zero
+fn easy_part<F: Field + Clone + PartialEq, I: BigInteger>(
+    f: &F,
+    base_order: &I,
+    half: u32,
+) -> Result<F, ErrorKind> {
+    let q = F::base_order();
+    let f_q_half = if !base_order.is_negative() && base_order.bits().eq(q.bits()) {
+        f.pow_q_power(half)
+    } else {
+        f.pow_bits(&base_order.pow(half).bits().collect::<Vec<_>>())
+    };
+    f_q_half
+        .checked_div(f)
+        .ok_or(ErrorKind::InvalidInput("Cannot exponentiate zero"))
+}
+
+/// Precomputed pairing parameters
+// Stores the order n, the embedding degree k and the final exponent (q^k - 1)/n, split as in
+// final_exponentiation, so that they are validated and computed once for a fixed curve
+// The parameters may use any BigInteger, the pairings themselves need rug integers for the
+// Miller loops
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairingContext<I: BigInteger = Integer> {
+    base_order: I,
+    order: I,
+    embedding_degree: u32,
+    easy: Option<u32>,
+
+    // Big endian bits of the hard part, or of the whole exponent when it is not split
+    hard_bits: Vec<bool>,
+}
+
+impl<I: BigInteger> PairingContext<I> {
+    /// New context for the base field order q
+    // Fails if the parameters are rejected by validate_pairing_params
+    pub fn new(q: &I, order: &I, embedding_degree: u32) -> Result<Self, ErrorKind> {
+        validate_pairing_params(q, order, embedding_degree)?;
+        let (easy, hard) = final_exponent(q, embedding_degree, order)?;
+        Ok(PairingContext {
+            base_order: q.clone(),
+            order: order.clone(),
+            embedding_degree,
+            easy,
+            hard_bits: hard.bits().collect(),
+        })
+    }
+
+    /// Group order
+    pub fn order(&self) -> &I {
+        &self.order
+    }
+
+    /// Embedding degree
+    pub fn embedding_degree(&self) -> u32 {
+        self.embedding_degree
+    }
+
+    /// Final exponentiation, see final_exponentiation
+    // Fails if f is zero and the exponent is split
+    pub fn final_exponentiation<F: Field + Clone + PartialEq>(
+        &self,
+        f: &F,
+    ) -> Result<F, ErrorKind> {
+        let f = match self.easy {
+            Some(half) => easy_part(f, &self.base_order, half)?,
+            None => f.clone(),
+        };
+        Ok(f.pow_bits(&self.hard_bits))
+    }
+}
+
+impl PairingContext {
+    /// Reduced Tate pairing, see tate_pairing
+    pub fn tate_pairing<F: Field + Clone + PartialEq>(
+        &self,
+        curve: &EllipticCurve<F>,
+        pt_p: &ECPoint<F>,
+        pt_q: &ECPoint<F>,
+    ) -> Result<Gt<F>, ErrorKind> {
+        let res = tate_miller(curve, pt_p, pt_q, &self.order)?;
+        Ok(Gt(self.final_exponentiation(&res)?))
+    }
+
+    /// Ate pairing, see ate_pairing
+    pub fn ate_pairing<F: Field + Clone + PartialEq>(
+        &self,
+        curve: &EllipticCurve<F>,
+        pt_p: &ECPoint<F>,
+        pt_q: &ECPoint<F>,
+        trace_m_1: &Integer,
+    ) -> Result<Gt<F>, ErrorKind> {
+        let res = miller(curve, pt_q, pt_p, trace_m_1)?;
+        Ok(Gt(self.final_exponentiation(&res)?))
+    }
+
+    /// Weil pairing, see weil_pairing
+    pub fn weil_pairing<F: Field + Clone + PartialEq>(
+        &self,
+        curve: &EllipticCurve<F>,
+        pt_p: &ECPoint<F>,
+        pt_q: &ECPoint<F>,
+    ) -> Result<Gt<F>, ErrorKind> {
+        weil_pairing(curve, pt_p.clone(), pt_q.clone(), self.order.clone())
+    }
+
+    /// Optimal ate pairing, see optimal_ate_pairing
+    pub fn optimal_ate_pairing<F: Field + Clone + PartialEq>(
+        &self,
+        curve: &EllipticCurve<F>,
+        pt_p: &ECPoint<F>,
+        pt_q: &ECPoint<F>,
+        loop_count: &Integer,
+        family: CurveFamily,
+    ) -> Result<Gt<F>, ErrorKind> {
+        let res = optimal_ate_miller(curve, pt_p, pt_q, loop_count, family)?;
+        Ok(Gt(self.final_exponentiation(&res)?))
     }
 }
 
 /// Miller's algorithm
 /// Returns f_{n,P}(Q) where div(f_{n,P}) = n(P) - ([n]P) - (n-1)(0)
+// Fails if Q is a zero or a pole of one of the lines, which is the case when Q is in the support
+// of the divisor of an intermediate function f_{i,P}
 pub fn miller<F: Field + Clone + PartialEq>(
     curve: &EllipticCurve<F>,
     pt_p: &ECPoint<F>,
     pt_q: &ECPoint<F>,
     n: &Integer,
 ) -> Result<F, ErrorKind> {
+    Ok(miller_loop(curve, pt_p, pt_q, n)?.0)
+}
+
+// Miller's algorithm, returns f_{n,P}(Q) along with [n]P
+fn miller_loop<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    n: &Integer,
+) -> Result<(F, ECPoint<F>), ErrorKind> {
     // Basic checks
     if pt_p == &ECPoint::PointAtInfinity {
         return Err(ErrorKind::InvalidInput("P must not be zero"));
@@ -50,50 +448,411 @@ pub fn miller<F: Field + Clone + PartialEq>(
         return Err(ErrorKind::InvalidInput("Q must not be zero"));
     }
     if n.is_zero() {
-        return Ok(F::one());
+        return Ok((F::one(), ECPoint::PointAtInfinity));
     }
 
     // Negative values of n are allowed, in which case
     // Q is evaluated instead at (v_{[n]P} f_{n,P)})^(-1)
     let sign = n.is_positive();
-    //let n = n.abs();
-    let nbits = n.abs_ref().complete().to_bits();
+    let n_abs = n.abs_ref().complete();
 
-    let one = F::one();
+    let mut t = F::one();
+    let mut pt_v = pt_p.clone();
+
+    // Miller loop, the leading bit is always set
+    for bit in n_abs.bits().skip(1) {
+        let (pt_s, ell) = curve.double_with_line(&pt_v, pt_q)?;
+        let vee = vertical(curve, &pt_s, pt_q)?;
+        check_support(&ell, &vee)?;
+        t = t.square().mul(&ell.div_unchecked(&vee));
+        pt_v = pt_s;
+
+        if bit {
+            let (pt_s, ell) = curve.add_with_line(&pt_v, pt_p, pt_q)?;
+            let vee = vertical(curve, &pt_s, pt_q)?;
+            check_support(&ell, &vee)?;
+            t = t.mul(&ell.div_unchecked(&vee));
+            pt_v = pt_s;
+        }
+    }
+
+    // Inversion for the Ate pairing, [n]P = -[|n|]P
+    if !sign {
+        let vee = vertical(curve, &pt_v, pt_q)?;
+        check_support(&F::one(), &vee)?;
+        t = t.mul(&vee).invert();
+        pt_v = curve.neg_point(&pt_v);
+    }
+
+    Ok((t, pt_v))
+}
+
+/// Point prepared for Miller loops with a fixed first argument
+// Stores the line functions of every step of the Miller loop for f_{n,P}, which only depend on P
+// and n, so that f_{n,P} can be evaluated at many Q without recomputing the multiples of P
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreparedPoint<F: Field> {
+    // For each step: whether it is a doubling step, then the line and the vertical line
+    steps: Vec<(bool, LineCoeffs<F>, LineCoeffs<F>)>,
+
+    // Vertical line through [n]P when n < 0, None otherwise
+    inversion: Option<LineCoeffs<F>>,
+}
+
+// Returns the coefficients of the vertical line through P
+fn vertical_coefficients<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+) -> Result<LineCoeffs<F>, ErrorKind> {
+    match pt_p {
+        ECPoint::PointAtInfinity => Ok((F::one(), F::zero(), F::zero())),
+        ECPoint::AffinePoint(_, _) => Ok(curve.line_coefficients(pt_p, &curve.invert(pt_p)?)),
+    }
+}
+
+// Evaluates the line c0 + cx x + cy y at (x, y)
+fn evaluate_line<F: Field + Clone>(coeffs: &LineCoeffs<F>, x: &F, y: &F) -> F {
+    let (c0, cx, cy) = coeffs;
+    c0.clone().add(&cx.clone().mul(x)).add(&cy.clone().mul(y))
+}
 
-    let mut t = one;
-    let mut i: usize = nbits.len() - 1; // Will not underflow because n != 0
+/// Precomputes the Miller loop for f_{n,P}
+pub fn prepare<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    n: &Integer,
+) -> Result<PreparedPoint<F>, ErrorKind> {
+    Ok(record_miller_loop(curve, pt_p, n)?.0)
+}
+
+// Runs the Miller loop for f_{n,P} without evaluating it, returns the lines of every step as in
+// prepare, and the multiple of P reached after each step
+fn record_miller_loop<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    n: &Integer,
+) -> Result<(PreparedPoint<F>, Vec<ECPoint<F>>), ErrorKind> {
+    if pt_p == &ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("P must not be zero"));
+    }
+
+    let mut steps = vec![];
+    let mut points = vec![];
     let mut pt_v = pt_p.clone();
 
-    if i != 0 {
-        i -= 1;
+    if !n.is_zero() {
+        let n_abs = n.abs_ref().complete();
 
-        // Miller loop
-        loop {
-            let pt_s = curve.double(&pt_v);
-            let ell = curve.line(&pt_v, &pt_v, pt_q)?;
-            let vee = curve.line(&pt_s, &curve.invert(&pt_s)?, pt_q)?;
-            t = t.square().mul(&ell.div(&vee));
+        // Same loop as miller, the leading bit is always set
+        for bit in n_abs.bits().skip(1) {
+            let (pt_s, ell) = curve.add_with_line_coefficients(&pt_v, &pt_v)?;
+            steps.push((true, ell, vertical_coefficients(curve, &pt_s)?));
+            points.push(pt_s.clone());
             pt_v = pt_s;
 
-            if nbits[i] {
-                let pt_s = curve.add(&pt_v, pt_p);
-                let ell = curve.line(&pt_v, pt_p, pt_q)?;
-                let vee = curve.line(&pt_s, &curve.invert(&pt_s)?, pt_q)?;
-                t = t.mul(&ell.div(&vee));
+            if bit {
+                let (pt_s, ell) = curve.add_with_line_coefficients(&pt_v, pt_p)?;
+                steps.push((false, ell, vertical_coefficients(curve, &pt_s)?));
+                points.push(pt_s.clone());
                 pt_v = pt_s;
             }
+        }
+    }
+
+    let inversion = if n.is_negative() {
+        Some(vertical_coefficients(curve, &pt_v)?)
+    } else {
+        None
+    };
+
+    Ok((PreparedPoint { steps, inversion }, points))
+}
+
+/// Miller function f_{n,P}, recorded step by step
+// Holds the same lines as PreparedPoint, along with the multiple of P reached after each step,
+// so that the loop can be inspected and the function evaluated at any number of points
+#[derive(Clone, Debug, PartialEq)]
+pub struct MillerFunction<F: Field + Clone> {
+    prepared: PreparedPoint<F>,
+    points: Vec<ECPoint<F>>,
+}
+
+impl<F: Field + Clone + PartialEq> MillerFunction<F> {
+    /// Records the Miller loop for f_{n,P}
+    pub fn new(
+        curve: &EllipticCurve<F>,
+        pt_p: &ECPoint<F>,
+        n: &Integer,
+    ) -> Result<Self, ErrorKind> {
+        let (prepared, points) = record_miller_loop(curve, pt_p, n)?;
+        Ok(MillerFunction { prepared, points })
+    }
+
+    /// Steps of the loop
+    // Whether it is a doubling step, the line, the vertical line and the point reached
+    pub fn steps(
+        &self,
+    ) -> impl Iterator<Item = (bool, &LineCoeffs<F>, &LineCoeffs<F>, &ECPoint<F>)> {
+        self.prepared
+            .steps
+            .iter()
+            .zip(&self.points)
+            .map(|((doubling, ell, vee), pt)| (*doubling, ell, vee, pt))
+    }
+
+    /// Returns f_{n,P}(Q)
+    // Fails if Q is not on the curve or is in the support of f_{n,P}
+    pub fn evaluate(&self, curve: &EllipticCurve<F>, pt_q: &ECPoint<F>) -> Result<F, ErrorKind> {
+        if !curve.is_on_curve(pt_q) {
+            return Err(ErrorKind::InvalidInput("Q is not on the curve"));
+        }
+        miller_with_prepared(&self.prepared, pt_q)
+    }
+}
+
+/// Miller's algorithm with a prepared first argument
+/// Returns f_{n,P}(Q) where P and n are those used to prepare the point
+// Fails like miller when Q is a zero or a pole of one of the lines
+pub fn miller_with_prepared<F: Field + Clone + PartialEq>(
+    prepared: &PreparedPoint<F>,
+    pt_q: &ECPoint<F>,
+) -> Result<F, ErrorKind> {
+    let (x_q, y_q) = pt_q
+        .xy()
+        .ok_or(ErrorKind::InvalidInput("Q must not be zero"))?;
+
+    let mut t = F::one();
+    for (doubling, ell, vee) in &prepared.steps {
+        if *doubling {
+            t = t.square();
+        }
+        let ell = evaluate_line(ell, x_q, y_q);
+        let vee = evaluate_line(vee, x_q, y_q);
+        check_support(&ell, &vee)?;
+        t = t.mul(&ell.div_unchecked(&vee));
+    }
+
+    // Inversion for the Ate pairing
+    if let Some(vee) = &prepared.inversion {
+        let vee = evaluate_line(vee, x_q, y_q);
+        check_support(&F::one(), &vee)?;
+        t = t.mul(&vee).invert();
+    }
+
+    Ok(t)
+}
+
+/// Miller's algorithm, without the vertical lines
+/// Returns f_{n,P}(Q) up to the vertical line factors, i.e. up to an element of Fq^(k/2)
+// Skips the evaluation of the vertical lines and the division at every step
+// /!\ Only meaningful when followed by a final exponentiation, with an even embedding degree k
+// and with the abscissa of Q in Fq^(k/2) (e.g. Q obtained from a twist or a distortion map),
+// as the vertical lines are then sent to 1
+// Fails if Q is a zero of one of the lines
+pub fn miller_no_denominator<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    n: &Integer,
+) -> Result<F, ErrorKind> {
+    // Basic checks
+    if pt_p == &ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("P must not be zero"));
+    }
+    if pt_q == &ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("Q must not be zero"));
+    }
+    if n.is_zero() {
+        return Ok(F::one());
+    }
+
+    let sign = n.is_positive();
+    let n_abs = n.abs_ref().complete();
+
+    let mut t = F::one();
+    let mut pt_v = pt_p.clone();
+
+    // Miller loop, the leading bit is always set
+    for bit in n_abs.bits().skip(1) {
+        let (pt_s, ell) = curve.double_with_line(&pt_v, pt_q)?;
+        check_support(&ell, &F::one())?;
+        t = t.square().mul(&ell);
+        pt_v = pt_s;
+
+        if bit {
+            let (pt_s, ell) = curve.add_with_line(&pt_v, pt_p, pt_q)?;
+            check_support(&ell, &F::one())?;
+            t = t.mul(&ell);
+            pt_v = pt_s;
+        }
+    }
+
+    // Inversion for the Ate pairing
+    if !sign {
+        t = t.invert();
+    }
+
+    Ok(t)
+}
+
+/// Miller's algorithm, using the non-adjacent form of n
+/// Returns f_{n,P}(Q) where div(f_{n,P}) = n(P) - ([n]P) - (n-1)(0)
+// Same result as miller, with fewer addition steps on average
+// A -1 digit is a subtraction step, using f_{m-1,P} = f_{m,P} l_{[m]P,-P} / (v_{[m-1]P} v_P)
+// Fails like miller when Q is a zero or a pole of one of the lines
+pub fn miller_naf<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    n: &Integer,
+) -> Result<F, ErrorKind> {
+    // Basic checks
+    if pt_p == &ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("P must not be zero"));
+    }
+    if pt_q == &ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("Q must not be zero"));
+    }
+    if n.is_zero() {
+        return Ok(F::one());
+    }
+
+    let sign = n.is_positive();
+    let naf = n.clone().to_naf();
+
+    // -P and the vertical line through P, only needed for subtraction steps
+    let pt_p_neg = curve.invert(pt_p)?;
+    let vee_p = vertical(curve, pt_p, pt_q)?;
+    check_support(&F::one(), &vee_p)?;
+
+    let mut t = F::one();
+    let mut pt_v = pt_p.clone();
 
-            if i == 0 {
-                break;
+    // Miller loop, the leading digit is always 1
+    for digit in naf.iter().rev().skip(1) {
+        let (pt_s, ell) = curve.double_with_line(&pt_v, pt_q)?;
+        let vee = vertical(curve, &pt_s, pt_q)?;
+        check_support(&ell, &vee)?;
+        t = t.square().mul(&ell.div_unchecked(&vee));
+        pt_v = pt_s;
+
+        match digit {
+            1 => {
+                let (pt_s, ell) = curve.add_with_line(&pt_v, pt_p, pt_q)?;
+                let vee = vertical(curve, &pt_s, pt_q)?;
+                check_support(&ell, &vee)?;
+                t = t.mul(&ell.div_unchecked(&vee));
+                pt_v = pt_s;
+            }
+            -1 => {
+                let (pt_s, ell) = curve.add_with_line(&pt_v, &pt_p_neg, pt_q)?;
+                let vee = vertical(curve, &pt_s, pt_q)?;
+                check_support(&ell, &vee)?;
+                t = t.mul(&ell.div_unchecked(&vee.mul(&vee_p)));
+                pt_v = pt_s;
             }
-            i -= 1;
+            _ => {}
         }
     }
 
     // Inversion for the Ate pairing
     if !sign {
-        let vee = curve.line(&pt_v, &curve.invert(&pt_v)?, pt_q)?;
+        let vee = vertical(curve, &pt_v, pt_q)?;
+        check_support(&F::one(), &vee)?;
+        t = t.mul(&vee).invert();
+    }
+
+    Ok(t)
+}
+
+/// Miller's algorithm, using the width-w non-adjacent form of n
+/// Returns f_{n,P}(Q) where div(f_{n,P}) = n(P) - ([n]P) - (n-1)(0)
+// Same result as miller. The odd multiples [d]P and the values f_{d,P}(Q) are precomputed for
+// |d| < 2^(w-1), then a digit d is an addition step
+// f_{m+d,P} = f_{m,P} f_{d,P} l_{[m]P,[d]P} / v_{[m+d]P}
+// Negative digits use f_{-d,P} = 1 / (f_{d,P} v_{[d]P})
+// The window w must be between 2 and 8, w = 2 gives the same steps as miller_naf
+pub fn miller_wnaf<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    n: &Integer,
+    w: usize,
+) -> Result<F, ErrorKind> {
+    // Basic checks
+    if !(2..=8).contains(&w) {
+        return Err(ErrorKind::InvalidInput(
+            "Window size must be between 2 and 8",
+        ));
+    }
+    if pt_p == &ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("P must not be zero"));
+    }
+    if pt_q == &ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("Q must not be zero"));
+    }
+    if n.is_zero() {
+        return Ok(F::one());
+    }
+
+    let sign = n.is_positive();
+    let naf = to_wnaf(n, w);
+
+    // ([d]P, f_{d,P}(Q)) for d = 1, 3, .. 2^(w-1) - 1,
+    // using f_{d+2,P} = f_{d,P} f_{2,P} l_{[d]P,[2]P} / v_{[d+2]P}
+    let (pt_2p, ell) = curve.double_with_line(pt_p, pt_q)?;
+    let vee = vertical(curve, &pt_2p, pt_q)?;
+    check_support(&ell, &vee)?;
+    let f_2 = ell.div_unchecked(&vee);
+    let mut odd = vec![(pt_p.clone(), F::one())];
+    for i in 1..(1 << (w - 2)) {
+        let (pt_d, f_d) = &odd[i - 1];
+        let (pt_s, ell) = curve.add_with_line(pt_d, &pt_2p, pt_q)?;
+        let vee = vertical(curve, &pt_s, pt_q)?;
+        check_support(&ell, &vee)?;
+        let f_s = f_d.clone().mul(&f_2).mul(&ell.div_unchecked(&vee));
+        odd.push((pt_s, f_s));
+    }
+    // ([-d]P, f_{-d,P}(Q))
+    let mut odd_neg = vec![];
+    for (pt_d, f_d) in &odd {
+        let vee = vertical(curve, pt_d, pt_q)?;
+        check_support(&F::one(), &vee)?;
+        odd_neg.push((curve.neg_point(pt_d), f_d.clone().mul(&vee).invert()));
+    }
+
+    // Miller loop, the leading digit is positive
+    let mut digits = naf.iter().rev();
+    let (mut pt_v, mut t) = match digits.next() {
+        Some(d) => odd[(*d as usize - 1) / 2].clone(),
+        None => return Ok(F::one()),
+    };
+    for digit in digits {
+        let (pt_s, ell) = curve.double_with_line(&pt_v, pt_q)?;
+        let vee = vertical(curve, &pt_s, pt_q)?;
+        check_support(&ell, &vee)?;
+        t = t.square().mul(&ell.div_unchecked(&vee));
+        pt_v = pt_s;
+
+        if *digit != 0 {
+            let index = (digit.unsigned_abs() as usize - 1) / 2;
+            let (pt_d, f_d) = if *digit > 0 {
+                &odd[index]
+            } else {
+                &odd_neg[index]
+            };
+            let (pt_s, ell) = curve.add_with_line(&pt_v, pt_d, pt_q)?;
+            let vee = vertical(curve, &pt_s, pt_q)?;
+            check_support(&ell, &vee)?;
+            t = t.mul(f_d).mul(&ell.div_unchecked(&vee));
+            pt_v = pt_s;
+        }
+    }
+
+    // Inversion for the Ate pairing
+    if !sign {
+        let vee = vertical(curve, &pt_v, pt_q)?;
+        check_support(&F::one(), &vee)?;
         t = t.mul(&vee).invert();
     }
 
@@ -108,25 +867,71 @@ pub fn weil_pairing<F: Field + Clone + PartialEq>(
     pt_p: ECPoint<F>,
     pt_q: ECPoint<F>,
     order: Integer,
-) -> Result<F, ErrorKind> {
+) -> Result<Gt<F>, ErrorKind> {
     let one = F::one();
 
     // P = Q, P = 0, or Q = 0
     if pt_p == pt_q || pt_p == ECPoint::PointAtInfinity || pt_q == ECPoint::PointAtInfinity {
-        return Ok(one);
+        return Ok(Gt(one));
     }
 
     // Weil pairing
-    let f_pq = miller(curve, &pt_p, &pt_q, &order)?;
-    let f_qp = miller(curve, &pt_q, &pt_p, &order)?;
-    let ratio = f_pq.div(&f_qp);
+    // The lines of the Miller loop only vanish at multiples of the first point, so Q is in the
+    // support of f_{n,P} (or P in that of f_{n,Q}) only when P and Q are linearly dependent,
+    // in which case the pairing is one
+    let (f_pq, f_qp) = match (
+        miller(curve, &pt_p, &pt_q, &order),
+        miller(curve, &pt_q, &pt_p, &order),
+    ) {
+        (Ok(f_pq), Ok(f_qp)) => (f_pq, f_qp),
+        (Err(IN_SUPPORT), _) | (_, Err(IN_SUPPORT)) => return Ok(Gt(one)),
+        (Err(err), _) | (_, Err(err)) => return Err(err),
+    };
+    let ratio = f_pq
+        .checked_div(&f_qp)
+        .ok_or(ErrorKind::InvalidInput("f_{n,Q}(P) must not be zero"))?;
 
-    // Sign correction if needed
+    // Sign correction: e_n(P, Q) = (-1)^n f_{n,P}(Q) / f_{n,Q}(P)
+    // so the ratio is negated exactly when n is odd
     if order.is_odd() {
-        Ok(ratio.neg())
+        Ok(Gt(ratio.neg()))
     } else {
-        Ok(ratio)
+        Ok(Gt(ratio))
+    }
+}
+
+// Fails unless P and Q are on the curve and [order]P = [order]Q = 0
+fn check_pairing_inputs<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    order: &Integer,
+) -> Result<(), ErrorKind> {
+    if !curve.is_on_curve(pt_p) {
+        return Err(ErrorKind::InvalidInput("P is not on the curve"));
+    }
+    if !curve.is_on_curve(pt_q) {
+        return Err(ErrorKind::InvalidInput("Q is not on the curve"));
+    }
+    if curve.mul(order, pt_p)? != ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("P is not killed by the order"));
+    }
+    if curve.mul(order, pt_q)? != ECPoint::PointAtInfinity {
+        return Err(ErrorKind::InvalidInput("Q is not killed by the order"));
     }
+    Ok(())
+}
+
+/// Weil pairing, with input validation
+// Same as weil_pairing, but first checks that P and Q are on the curve and that [order]P = [order]Q = 0
+pub fn weil_pairing_checked<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: ECPoint<F>,
+    pt_q: ECPoint<F>,
+    order: Integer,
+) -> Result<Gt<F>, ErrorKind> {
+    check_pairing_inputs(curve, &pt_p, &pt_q, &order)?;
+    weil_pairing(curve, pt_p, pt_q, order)
 }
 
 /// Reduced Tate pairing
@@ -140,31 +945,66 @@ pub fn tate_pairing<F: Field + Clone + PartialEq>(
     pt_p: &ECPoint<F>,
     pt_q: &ECPoint<F>,
     order: &Integer,
-    embedding_degree: &Integer,
-) -> Result<F, &'static str> {
+    embedding_degree: u32,
+) -> Result<Gt<F>, ErrorKind> {
     let q = F::base_order();
+    let res = tate_miller(curve, pt_p, pt_q, order)?;
+    Ok(Gt(final_exponentiation(&res, &q, embedding_degree, order)?))
+}
 
+// Returns f_{n,P}(Q), or f_{n,P}(Q + R) / f_{n,P}(R) for a random R when Q is in the support of
+// f_{n,P}: both give the same reduced Tate pairing
+fn tate_miller<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    order: &Integer,
+) -> Result<F, ErrorKind> {
     // Check whether we need to move poles
-    if let Ok(res) = miller(curve, pt_p, pt_q, order) {
+    match miller(curve, pt_p, pt_q, order) {
         // We don't
-        let one: Integer = 1.into();
-        let e = q.large_pow(embedding_degree).sub(one).div(order);
-        Ok(res.pow(&e))
-    } else {
-        // We do
+        Ok(res) => return Ok(res),
+        Err(IN_SUPPORT) => {}
+        Err(err) => return Err(err),
+    }
 
-        let pt_r = curve.clone().random_point();
-        let f_qr = tate_pairing(
-            curve,
-            pt_p,
-            &curve.add(pt_q, &pt_r),
-            order,
-            embedding_degree,
-        )?;
-        let f_r = tate_pairing(curve, pt_p, &pt_r, order, embedding_degree)?;
+    // We do
+    for _ in 0..POLE_MOVING_ATTEMPTS {
+        let pt_r = curve.random_point()?;
+        let pt_qr = curve.add(pt_q, &pt_r)?;
 
-        Ok(f_qr.div(&f_r))
+        match (
+            miller(curve, pt_p, &pt_qr, order),
+            miller(curve, pt_p, &pt_r, order),
+        ) {
+            (Ok(f_qr), Ok(f_r)) => {
+                return f_qr
+                    .checked_div(&f_r)
+                    .ok_or(ErrorKind::InvalidInput("f_{n,P}(R) must not be zero"))
+            }
+            (Err(IN_SUPPORT), _) | (_, Err(IN_SUPPORT)) => {}
+            (Err(err), _) | (_, Err(err)) => return Err(err),
+        }
     }
+
+    Err(ErrorKind::InvalidInput(
+        "Could not move the poles away from Q",
+    ))
+}
+
+/// Reduced Tate pairing, with input validation
+// Same as tate_pairing, but first checks the parameters with validate_pairing_params, then that
+// P and Q are on the curve and that [order]P = [order]Q = 0
+pub fn tate_pairing_checked<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    order: &Integer,
+    embedding_degree: u32,
+) -> Result<Gt<F>, ErrorKind> {
+    validate_pairing_params(&F::base_order(), order, embedding_degree)?;
+    check_pairing_inputs(curve, pt_p, pt_q, order)?;
+    tate_pairing(curve, pt_p, pt_q, order, embedding_degree)
 }
 
 /// N-th modified Ate pairing
@@ -176,18 +1016,685 @@ pub fn tate_pairing<F: Field + Clone + PartialEq>(
 // P is on E/Fq
 // Q is on E/Fq^k where k = embedding degree
 // P in ker(Frob - 1)
-// Q in ker(Frob - q)
+// Q in ker(Frob - q), such a Q is obtained with EllipticCurve::map_to_trace_zero
+// A compact Q on a twist E'/Fq^(k/d) is brought to E/Fq^k with Twist::untwist
 pub fn ate_pairing<F: Field + Clone + PartialEq>(
     curve: &EllipticCurve<F>,
     pt_p: &ECPoint<F>,
     pt_q: &ECPoint<F>,
     order: &Integer,
-    embedding_degree: &Integer,
+    embedding_degree: u32,
     trace_m_1: &Integer,
-) -> Result<F, ErrorKind> {
+) -> Result<Gt<F>, ErrorKind> {
     let q = F::base_order();
     let res = miller(curve, pt_q, pt_p, trace_m_1)?;
-    let one: Integer = 1.into();
-    let e = q.large_pow(embedding_degree).sub(one).div(order);
-    Ok(res.pow(&e))
+    Ok(Gt(final_exponentiation(&res, &q, embedding_degree, order)?))
+}
+
+/// Optimal ate pairing
+// Returns f^e where e = (q^k - 1)/n with q = base field size, k = embedding degree and
+// n = group order, and f is the optimal Miller function of the curve family evaluated at P:
+// f = f_{c,Q} for BLS curves, with c = x
+// f = f_{c,Q} l_{[c]Q,π(Q)} l_{[c]Q+π(Q),-π²(Q)} for BN curves, with c = 6u + 2, π the Frobenius
+// The Miller loop runs over the optimal coefficient c rather than over the trace, which makes it
+// much shorter
+// /!\ No checks
+// P and Q on same curve, both of order n
+// P in G1 = E[n] ∩ ker(Frob - 1), i.e. P is on E/Fq
+// Q in G2 = E[n] ∩ ker(Frob - q), i.e. Q is on E/Fq^k
+// For BN curves the abscissas of G2 must lie in Fq^(k/2), as for Q obtained with Twist::untwist,
+// since the vertical lines of the two extra steps are left out
+pub fn optimal_ate_pairing<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    loop_count: &Integer,
+    family: CurveFamily,
+    embedding_degree: u32,
+    group_order: &Integer,
+) -> Result<Gt<F>, ErrorKind> {
+    let q = F::base_order();
+    let res = optimal_ate_miller(curve, pt_p, pt_q, loop_count, family)?;
+    Ok(Gt(final_exponentiation(
+        &res,
+        &q,
+        embedding_degree,
+        group_order,
+    )?))
+}
+
+// Optimal Miller function of the curve family evaluated at P, see optimal_ate_pairing
+fn optimal_ate_miller<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    loop_count: &Integer,
+    family: CurveFamily,
+) -> Result<F, ErrorKind> {
+    let (f, pt_t) = miller_loop(curve, pt_q, pt_p, loop_count)?;
+    match family {
+        CurveFamily::Bls => Ok(f),
+        CurveFamily::Bn => {
+            // [c]Q + π(Q) - π²(Q) + π³(Q) = 0, so neither line is vertical
+            let pt_q1 = curve.frobenius(pt_q);
+            let pt_q2 = curve.neg_point(&curve.frobenius(&pt_q1));
+            let (pt_t, ell_1) = curve.add_with_line(&pt_t, &pt_q1, pt_p)?;
+            let (_, ell_2) = curve.add_with_line(&pt_t, &pt_q2, pt_p)?;
+            check_support(&ell_1, &ell_2)?;
+            Ok(f.mul(&ell_1).mul(&ell_2))
+        }
+    }
+}
+
+/// Incremental product of reduced Tate pairings
+// Same as multi_pairing, for pairs that are not all known up front: the Miller loops are
+// accumulated as the pairs come, and the final exponentiation is applied by finish
+// /!\ Same (lack of) checks as tate_pairing, and poles are not moved
+#[derive(Clone, Debug)]
+pub struct MillerAccumulator<'a, F: Field> {
+    curve: &'a EllipticCurve<F>,
+    order: Integer,
+    acc: F,
+}
+
+impl<'a, F: Field + Clone + PartialEq> MillerAccumulator<'a, F> {
+    /// New accumulator, for points of the given order
+    pub fn new(curve: &'a EllipticCurve<F>, order: &Integer) -> Self {
+        MillerAccumulator {
+            curve,
+            order: order.clone(),
+            acc: F::one(),
+        }
+    }
+
+    /// Multiplies the running product by f_{n,P}(Q)
+    pub fn add_pair(&mut self, pt_p: &ECPoint<F>, pt_q: &ECPoint<F>) -> Result<(), ErrorKind> {
+        let f = miller(self.curve, pt_p, pt_q, &self.order)?;
+        self.acc = self.acc.clone().mul(&f);
+        Ok(())
+    }
+
+    /// Running product, without the final exponentiation
+    pub fn unreduced(&self) -> UnreducedPairing<F> {
+        UnreducedPairing(self.acc.clone())
+    }
+
+    /// Applies the final exponentiation to the running product
+    // Fails if the order does not divide q^k - 1
+    pub fn finish(self, embedding_degree: u32) -> Result<Gt<F>, ErrorKind> {
+        self.unreduced().reduce(embedding_degree, &self.order)
+    }
+}
+
+/// Product of reduced Tate pairings
+// Returns Π f_{n,Pi}(Qi)^e where e = (q^k - 1)/n with q = base field size, n = order,
+// and k = embedding degree
+// The Miller loops are accumulated and a single final exponentiation is applied to the product,
+// which is much cheaper than multiplying separately computed pairings
+// /!\ Same (lack of) checks as tate_pairing, and poles are not moved
+pub fn multi_pairing<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pairs: &[(ECPoint<F>, ECPoint<F>)],
+    order: &Integer,
+    embedding_degree: u32,
+) -> Result<Gt<F>, ErrorKind> {
+    let mut acc = MillerAccumulator::new(curve, order);
+    for (pt_p, pt_q) in pairs {
+        acc.add_pair(pt_p, pt_q)?;
+    }
+    acc.finish(embedding_degree)
+}
+
+/// Pairing equation check
+// Returns whether e(A, B) = e(C, D), computed as e(A, B) * e(-C, D) = 1 with a single
+// multi-pairing, so that no division is needed
+// Pairs involving the zero point are skipped, as they pair to 1
+pub fn pairing_check<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_a: &ECPoint<F>,
+    pt_b: &ECPoint<F>,
+    pt_c: &ECPoint<F>,
+    pt_d: &ECPoint<F>,
+    order: &Integer,
+    embedding_degree: u32,
+) -> Result<bool, ErrorKind> {
+    let mut pairs = vec![];
+    if pt_a != &ECPoint::PointAtInfinity && pt_b != &ECPoint::PointAtInfinity {
+        pairs.push((pt_a.clone(), pt_b.clone()));
+    }
+    if pt_c != &ECPoint::PointAtInfinity && pt_d != &ECPoint::PointAtInfinity {
+        pairs.push((curve.invert(pt_c)?, pt_d.clone()));
+    }
+
+    let res = multi_pairing(curve, &pairs, order, embedding_degree)?;
+    Ok(res.is_one())
+}
+
+/// Bilinearity self-test
+// Returns whether e([a]P, [b]Q) = e(P, Q)^(ab) for the selected pairing
+// A false result means that some parameter (order, embedding degree, loop count...) is wrong,
+// including an order not dividing q^k - 1, which the final exponentiation would reject
+// /!\ A degenerate pairing, e.g. e(P, Q) = 1, passes trivially
+#[allow(clippy::too_many_arguments)]
+pub fn verify_bilinear<F: Field + Clone + PartialEq>(
+    curve: &EllipticCurve<F>,
+    pt_p: &ECPoint<F>,
+    pt_q: &ECPoint<F>,
+    order: &Integer,
+    embedding_degree: u32,
+    a: &Integer,
+    b: &Integer,
+    pairing: &PairingKind,
+) -> Result<bool, ErrorKind> {
+    if *pairing != PairingKind::Weil
+        && validate_pairing_params(&F::base_order(), order, embedding_degree).is_err()
+    {
+        return Ok(false);
+    }
+
+    let pair = |pt_p: &ECPoint<F>, pt_q: &ECPoint<F>| match pairing {
+        PairingKind::Weil => weil_pairing(curve, pt_p.clone(), pt_q.clone(), order.clone()),
+        PairingKind::Tate => tate_pairing(curve, pt_p, pt_q, order, embedding_degree),
+        PairingKind::Ate { trace_m_1 } => {
+            ate_pairing(curve, pt_p, pt_q, order, embedding_degree, trace_m_1)
+        }
+        PairingKind::OptimalAte { loop_count, family } => optimal_ate_pairing(
+            curve,
+            pt_p,
+            pt_q,
+            loop_count,
+            *family,
+            embedding_degree,
+            order,
+        ),
+    };
+
+    let lhs = pair(&curve.mul(a, pt_p)?, &curve.mul(b, pt_q)?)?;
+    let rhs = pair(pt_p, pt_q)?.pow(&(a * b).complete());
+    Ok(lhs == rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        params::bn254,
+        test_fields::{Fp, Fp2},
+    };
+    use rug::ops::Pow;
+
+    type K = Fp2<103>;
+
+    // Order of the pairing groups and cofactor on the curve below
+    const R: i64 = 13;
+    const COFACTOR: i64 = 8;
+
+    // Supersingular y² = x³ + x over F103, with 104 = 8 × 13 points and embedding degree 2
+    fn curve() -> EllipticCurve<K> {
+        EllipticCurve::new((
+            K::zero(),
+            K::zero(),
+            K::zero(),
+            K::one(),
+            K::zero(),
+            K::zero(),
+        ))
+        .unwrap()
+    }
+
+    // Point of order 13 with coords in F103, the first abscissa from `seed` on
+    fn base_point(curve: &EllipticCurve<K>, seed: i64) -> ECPoint<K> {
+        (seed..)
+            .find_map(|x| {
+                let x = Fp::<103>::new(x);
+                let y = x.clone().square().add(&Fp::one()).mul(&x).sqrt();
+                let pt = curve.point(Fp2(x, Fp::zero()), Fp2(y, Fp::zero())).ok()?;
+                let pt = curve.mul(&Integer::from(COFACTOR), &pt).ok()?;
+                (pt != ECPoint::PointAtInfinity).then_some(pt)
+            })
+            .unwrap()
+    }
+
+    // Distortion map (x, y) ↦ (-x, iy), sending the points over F103 out of it
+    fn distort(pt: &ECPoint<K>) -> ECPoint<K> {
+        match pt {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(x, y) => {
+                ECPoint::AffinePoint(x.clone().neg(), y.clone().mul(&K::new(0, 1)))
+            }
+        }
+    }
+
+    #[test]
+    fn optimal_ate_is_bilinear() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&pt_p);
+        // q = 103 and t - 1 = -1 = 12 mod 13
+        for loop_count in [Integer::from(103), Integer::from(12)] {
+            let pair = |pt_p: &ECPoint<K>, pt_q: &ECPoint<K>| {
+                optimal_ate_pairing(&curve, pt_p, pt_q, &loop_count, CurveFamily::Bls, 2, &r)
+                    .unwrap()
+            };
+            let e = pair(&pt_p, &pt_q);
+            assert!(!e.is_one());
+            assert!(e.clone().pow(&r).is_one());
+            for (a, b) in [(2, 3), (5, 7), (11, 4)] {
+                let pt_a = curve.mul(&Integer::from(a), &pt_p).unwrap();
+                let pt_b = curve.mul(&Integer::from(b), &pt_q).unwrap();
+                assert_eq!(pair(&pt_a, &pt_b), e.clone().pow(&Integer::from(a * b)));
+            }
+        }
+    }
+
+    #[test]
+    fn verify_bilinear_detects_wrong_parameters() {
+        let curve = curve();
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&pt_p);
+        let (a, b) = (Integer::from(5), Integer::from(7));
+        let kinds = [
+            PairingKind::Weil,
+            PairingKind::Tate,
+            PairingKind::Ate {
+                trace_m_1: Integer::from(-1),
+            },
+        ];
+        let verify = |order: i64, k: u32, kind: &PairingKind| {
+            verify_bilinear(&curve, &pt_p, &pt_q, &Integer::from(order), k, &a, &b, kind)
+        };
+        for kind in &kinds {
+            assert_eq!(verify(R, 2, kind), Ok(true));
+            // 17 divides q² - 1 but not the group order
+            assert_eq!(verify(17, 2, kind), Ok(false));
+        }
+        // 13 does not divide q - 1 nor q³ - 1, the Weil pairing does not depend on k
+        for k in [1, 3] {
+            assert_eq!(verify(R, k, &kinds[0]), Ok(true));
+            assert_eq!(verify(R, k, &kinds[1]), Ok(false));
+            assert_eq!(verify(R, k, &kinds[2]), Ok(false));
+        }
+    }
+
+    #[test]
+    fn checked_pairings_reject_invalid_points() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&base_point(&curve, 7));
+        let off_curve = ECPoint::AffinePoint(K::one(), K::one());
+        // (0, 0) is on the curve, of order 2
+        let two_torsion = curve.point(K::zero(), K::zero()).unwrap();
+
+        let weil = |p: &ECPoint<K>, q: &ECPoint<K>| {
+            weil_pairing_checked(&curve, p.clone(), q.clone(), r.clone())
+        };
+        let tate = |p: &ECPoint<K>, q: &ECPoint<K>| tate_pairing_checked(&curve, p, q, &r, 2);
+        assert_eq!(
+            weil(&pt_p, &pt_q),
+            weil_pairing(&curve, pt_p.clone(), pt_q.clone(), r.clone())
+        );
+        assert_eq!(
+            tate(&pt_p, &pt_q),
+            tate_pairing(&curve, &pt_p, &pt_q, &r, 2)
+        );
+        for (p, q, msg) in [
+            (&off_curve, &pt_q, "P is not on the curve"),
+            (&pt_p, &off_curve, "Q is not on the curve"),
+            (&two_torsion, &pt_q, "P is not killed by the order"),
+            (&pt_p, &two_torsion, "Q is not killed by the order"),
+        ] {
+            assert_eq!(weil(p, q), Err(ErrorKind::InvalidInput(msg)));
+            assert_eq!(tate(p, q), Err(ErrorKind::InvalidInput(msg)));
+        }
+    }
+
+    #[test]
+    fn miller_accumulator_is_product_of_pairings() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let pairs: Vec<_> = [(3, 7), (5, 11), (20, 2)]
+            .into_iter()
+            .map(|(a, b)| (base_point(&curve, a), distort(&base_point(&curve, b))))
+            .collect();
+
+        let mut acc = MillerAccumulator::new(&curve, &r);
+        let mut product = Gt::one();
+        for (pt_p, pt_q) in &pairs {
+            acc.add_pair(pt_p, pt_q).unwrap();
+            product = product * tate_pairing(&curve, pt_p, pt_q, &r, 2).unwrap();
+            assert_eq!(acc.unreduced().reduce(2, &r).unwrap(), product);
+        }
+        assert!(!product.is_one());
+        assert_eq!(acc.finish(2).unwrap(), product);
+        assert!(MillerAccumulator::new(&curve, &r)
+            .finish(2)
+            .unwrap()
+            .is_one());
+    }
+
+    #[test]
+    fn weil_of_dependent_points_is_one() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let pt_p = base_point(&curve, 3);
+        let weil = |a: i64, b: i64| {
+            let pt_a = curve.mul(&Integer::from(a), &pt_p).unwrap();
+            let pt_b = curve.mul(&Integer::from(b), &pt_p).unwrap();
+            weil_pairing(&curve, pt_a, pt_b, r.clone()).unwrap()
+        };
+        for (a, b) in [(1, 2), (2, 1), (3, 5), (1, 12), (4, 4)] {
+            assert!(weil(a, b).is_one(), "a = {a}, b = {b}");
+        }
+        // Independent points do not pair to one
+        let pt_q = distort(&curve.mul(&Integer::from(2), &pt_p).unwrap());
+        assert!(!weil_pairing(&curve, pt_p.clone(), pt_q, r)
+            .unwrap()
+            .is_one());
+    }
+
+    #[test]
+    fn unreduced_product_then_reduce() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let (pt_p, pt_p2) = (base_point(&curve, 3), base_point(&curve, 7));
+        let (pt_q, pt_q2) = (
+            distort(&base_point(&curve, 5)),
+            distort(&base_point(&curve, 11)),
+        );
+        let unreduced = |pt_p: &ECPoint<K>, pt_q: &ECPoint<K>| {
+            UnreducedPairing::new(tate_miller(&curve, pt_p, pt_q, &r).unwrap())
+        };
+
+        let e = tate_pairing(&curve, &pt_p, &pt_q, &r, 2).unwrap();
+        let e2 = tate_pairing(&curve, &pt_p2, &pt_q2, &r, 2).unwrap();
+        let product = unreduced(&pt_p, &pt_q) * &unreduced(&pt_p2, &pt_q2);
+        assert_eq!(product.reduce(2, &r), Ok(e.clone() * e2));
+        assert_eq!(unreduced(&pt_p, &pt_q).reduce(2, &r), Ok(e.clone()));
+        assert_eq!(
+            (UnreducedPairing::one() * unreduced(&pt_p, &pt_q)).reduce(2, &r),
+            Ok(e)
+        );
+    }
+
+    #[test]
+    fn bits_match_indexed_loop() {
+        // Big endian bits, as read by the indexed loop the iterator replaced
+        fn indexed<I: BigInteger>(n: &I) -> Vec<bool> {
+            let little: Vec<bool> = (0..n.significant_bits()).map(|i| n.get_bit(i)).collect();
+            let mut big = vec![];
+            let mut i = little.len();
+            while i > 0 {
+                i -= 1;
+                big.push(little[i]);
+            }
+            big
+        }
+        let two_64 = Integer::from(1) << 64u32;
+        for n in [
+            Integer::new(),
+            Integer::from(1),
+            Integer::from(13),
+            Integer::from(-13),
+            two_64.clone(),
+            two_64 - 1u32,
+            Integer::u_pow_u(3, 161).complete(),
+        ] {
+            assert_eq!(n.bits().collect::<Vec<_>>(), indexed(&n), "n = {n}");
+        }
+        for n in [0, 1, 13, -13, 1 << 40, i64::MAX] {
+            let n = crate::bigint::BigInt::from_i64(n);
+            assert_eq!(n.bits().collect::<Vec<_>>(), indexed(&n));
+        }
+        assert_eq!(Integer::new().bits().count(), 0);
+        assert_eq!(
+            Integer::from(8).bits().collect::<Vec<_>>(),
+            [true, false, false, false]
+        );
+    }
+
+    #[test]
+    fn multi_pairing_is_product() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let pt_p1 = base_point(&curve, 3);
+        let pt_p2 = base_point(&curve, 7);
+        let pt_q1 = distort(&curve.mul(&Integer::from(4), &pt_p1).unwrap());
+        let pt_q2 = distort(&pt_p2);
+        let product = tate_pairing(&curve, &pt_p1, &pt_q1, &r, 2).unwrap()
+            * tate_pairing(&curve, &pt_p2, &pt_q2, &r, 2).unwrap();
+        let pairs = [(pt_p1, pt_q1), (pt_p2, pt_q2)];
+        assert_eq!(multi_pairing(&curve, &pairs, &r, 2).unwrap(), product);
+    }
+
+    #[test]
+    fn pairing_check_relations() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&pt_p);
+        let mul = |k: i64, pt: &ECPoint<K>| curve.mul(&Integer::from(k), pt).unwrap();
+        let zero = ECPoint::PointAtInfinity;
+        // e([6]P, [5]Q) = e([10]P, [3]Q)
+        let (pt_a, pt_b, pt_c, pt_d) =
+            (mul(6, &pt_p), mul(5, &pt_q), mul(10, &pt_p), mul(3, &pt_q));
+        assert!(pairing_check(&curve, &pt_a, &pt_b, &pt_c, &pt_d, &r, 2).unwrap());
+        assert!(!pairing_check(&curve, &pt_a, &pt_b, &pt_c, &pt_q, &r, 2).unwrap());
+        assert!(pairing_check(&curve, &pt_a, &zero, &zero, &pt_d, &r, 2).unwrap());
+        assert!(!pairing_check(&curve, &pt_a, &pt_b, &zero, &pt_d, &r, 2).unwrap());
+    }
+
+    #[test]
+    fn miller_naf_agrees_with_miller() {
+        let curve = curve();
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&base_point(&curve, 7));
+        for n in -40..300 {
+            let n = Integer::from(n);
+            let expected = miller(&curve, &pt_p, &pt_q, &n).unwrap();
+            assert_eq!(miller_naf(&curve, &pt_p, &pt_q, &n).unwrap(), expected);
+            for w in 2..=5 {
+                assert_eq!(miller_wnaf(&curve, &pt_p, &pt_q, &n, w).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn miller_naf_in_support() {
+        let curve = curve();
+        let pt_p = base_point(&curve, 3);
+        let pt_2p = curve.mul(&Integer::from(2), &pt_p).unwrap();
+        // 3 = 4 - 1, the vertical line through [2]P vanishes at [2]P
+        let n = Integer::from(3);
+        assert_eq!(miller(&curve, &pt_p, &pt_2p, &n), Err(IN_SUPPORT));
+        assert_eq!(miller_naf(&curve, &pt_p, &pt_2p, &n), Err(IN_SUPPORT));
+    }
+
+    #[test]
+    fn miller_no_denominator_after_final_exponentiation() {
+        let curve = curve();
+        let (q, r) = (Integer::from(103), Integer::from(R));
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&base_point(&curve, 7));
+        let reduce = |f: K| final_exponentiation(&f, &q, 2, &r).unwrap();
+        for n in [R, 2 * R, -R, 103] {
+            let n = Integer::from(n);
+            assert_eq!(
+                reduce(miller_no_denominator(&curve, &pt_p, &pt_q, &n).unwrap()),
+                reduce(miller(&curve, &pt_p, &pt_q, &n).unwrap())
+            );
+        }
+        // The tangent line at P vanishes at P
+        let n = Integer::from(2);
+        assert_eq!(
+            miller_no_denominator(&curve, &pt_p, &pt_p, &n),
+            Err(IN_SUPPORT)
+        );
+    }
+
+    #[test]
+    fn miller_with_prepared_agrees_with_miller() {
+        let curve = curve();
+        let pt_p = base_point(&curve, 3);
+        for n in [-27, -1, 1, 12, 13, 103, 250] {
+            let n = Integer::from(n);
+            let prepared = prepare(&curve, &pt_p, &n).unwrap();
+            for seed in [5, 7, 11] {
+                let pt_q = distort(&base_point(&curve, seed));
+                assert_eq!(
+                    miller_with_prepared(&prepared, &pt_q).unwrap(),
+                    miller(&curve, &pt_p, &pt_q, &n).unwrap()
+                );
+            }
+        }
+        // The vertical line through [2]P vanishes at [2]P
+        let pt_2p = curve.mul(&Integer::from(2), &pt_p).unwrap();
+        let prepared = prepare(&curve, &pt_p, &Integer::from(3)).unwrap();
+        assert_eq!(miller_with_prepared(&prepared, &pt_2p), Err(IN_SUPPORT));
+    }
+
+    #[test]
+    fn miller_function_evaluate_agrees_with_miller() {
+        let curve = curve();
+        let pt_p = base_point(&curve, 3);
+        for n in [-27, 1, 12, 103] {
+            let n = Integer::from(n);
+            let f = MillerFunction::new(&curve, &pt_p, &n).unwrap();
+            for seed in [5, 7, 11] {
+                let pt_q = distort(&base_point(&curve, seed));
+                assert_eq!(
+                    f.evaluate(&curve, &pt_q).unwrap(),
+                    miller(&curve, &pt_p, &pt_q, &n).unwrap()
+                );
+            }
+        }
+        // The last step reaches [n]P
+        let n = Integer::from(12);
+        let f = MillerFunction::new(&curve, &pt_p, &n).unwrap();
+        let (_, _, _, last) = f.steps().last().unwrap();
+        assert_eq!(*last, curve.mul(&n, &pt_p).unwrap());
+        // Q off the curve
+        let pt_q = ECPoint::AffinePoint(K::one(), K::one());
+        assert!(f.evaluate(&curve, &pt_q).is_err());
+    }
+
+    #[test]
+    fn context_agrees_with_free_pairings() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let context = PairingContext::new(&Integer::from(103), &r, 2).unwrap();
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&base_point(&curve, 5));
+        assert_eq!(
+            context.tate_pairing(&curve, &pt_p, &pt_q).unwrap(),
+            tate_pairing(&curve, &pt_p, &pt_q, &r, 2).unwrap()
+        );
+        // t - 1 = -1
+        let trace_m_1 = Integer::from(-1);
+        assert_eq!(
+            context
+                .ate_pairing(&curve, &pt_p, &pt_q, &trace_m_1)
+                .unwrap(),
+            ate_pairing(&curve, &pt_p, &pt_q, &r, 2, &trace_m_1).unwrap()
+        );
+        assert_eq!(
+            context.weil_pairing(&curve, &pt_p, &pt_q).unwrap(),
+            weil_pairing(&curve, pt_p.clone(), pt_q.clone(), r.clone()).unwrap()
+        );
+        let loop_count = Integer::from(103);
+        assert_eq!(
+            context
+                .optimal_ate_pairing(&curve, &pt_p, &pt_q, &loop_count, CurveFamily::Bls)
+                .unwrap(),
+            optimal_ate_pairing(&curve, &pt_p, &pt_q, &loop_count, CurveFamily::Bls, 2, &r)
+                .unwrap()
+        );
+        assert!(!context.weil_pairing(&curve, &pt_p, &pt_q).unwrap().is_one());
+    }
+
+    #[test]
+    fn weil_is_antisymmetric() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&base_point(&curve, 7));
+        let e_pq = weil_pairing(&curve, pt_p.clone(), pt_q.clone(), r.clone()).unwrap();
+        let e_qp = weil_pairing(&curve, pt_q.clone(), pt_p.clone(), r.clone()).unwrap();
+        assert!(!e_pq.is_one());
+        assert!((e_pq.clone() * e_qp).is_one());
+        assert!(e_pq.pow(&r).is_one());
+        assert!(weil_pairing(&curve, pt_p.clone(), pt_p, r)
+            .unwrap()
+            .is_one());
+    }
+
+    #[test]
+    fn tate_moves_poles() {
+        let curve = curve();
+        let r = Integer::from(R);
+        let pt_p = base_point(&curve, 3);
+        let pt_q = distort(&base_point(&curve, 7));
+        // [2]P is in the support of f_{r,P}, the pairing of two points over F103 is one
+        let pt_2p = curve.mul(&Integer::from(2), &pt_p).unwrap();
+        assert!(tate_pairing(&curve, &pt_p, &pt_2p, &r, 2).unwrap().is_one());
+        // Moving the poles gives the same value when Q is not in the support
+        let pt_2pq = curve.add(&pt_2p, &pt_q).unwrap();
+        assert_eq!(
+            tate_pairing(&curve, &pt_p, &pt_2pq, &r, 2).unwrap(),
+            tate_pairing(&curve, &pt_p, &pt_q, &r, 2).unwrap()
+        );
+        // Other errors are passed through
+        let zero = ECPoint::PointAtInfinity;
+        assert_eq!(
+            tate_pairing(&curve, &zero, &pt_q, &r, 2),
+            Err(ErrorKind::InvalidInput("P must not be zero"))
+        );
+    }
+
+    #[test]
+    fn embedding_degree_values() {
+        let int = Integer::from;
+        assert_eq!(embedding_degree(&int(103), &int(13)), Ok(2));
+        assert_eq!(embedding_degree(&int(103), &int(7)), Ok(6));
+        assert!(embedding_degree(&int(103), &int(1)).is_err());
+        assert!(embedding_degree(&int(26), &int(13)).is_err());
+        let (q, r) = (bn254::base_prime(), bn254::group_order());
+        assert_eq!(embedding_degree(&q, &r), Ok(12));
+        assert!(validate_pairing_params(&q, &r, 12).is_ok());
+        assert!(validate_pairing_params(&q, &r, 6).is_err());
+    }
+
+    #[test]
+    fn final_exponentiation_matches_direct_power() {
+        let f = K::new(17, 42);
+        let r = Integer::from(R);
+        for (q, k) in [(103, 2), (103, 4), (3, 3), (5, 4)] {
+            let q = Integer::from(q);
+            let e = (q.clone().pow(k) - 1u32) / &r;
+            let (easy, hard) = final_exponent(&q, k, &r).unwrap();
+            match easy {
+                Some(half) => assert_eq!((q.clone().pow(half) - 1u32) * hard, e),
+                None => assert_eq!(hard, e),
+            }
+            assert_eq!(
+                final_exponentiation(&f, &q, k, &r),
+                Ok(f.clone().pow(&e.clone()))
+            );
+        }
+        // 13 does not divide 103^3 - 1, nor 7^2 - 1
+        for (q, k) in [(103, 3), (7, 2)] {
+            let q = Integer::from(q);
+            assert!(final_exponent(&q, k, &r).is_err());
+            assert!(final_exponentiation(&f, &q, k, &r).is_err());
+        }
+        assert!(final_exponent(&Integer::from(103), 2, &Integer::from(0)).is_err());
+        assert!(UnreducedPairing::new(f.clone()).reduce(3, &r).is_err());
+        // The easy part divides by f
+        let q = Integer::from(103);
+        assert!(final_exponentiation(&K::zero(), &q, 2, &r).is_err());
+        let context = PairingContext::new(&Integer::from(103), &r, 2).unwrap();
+        let e = (Integer::from(103 * 103) - 1u32) / &r;
+        assert_eq!(context.final_exponentiation(&f), Ok(f.pow(&e)));
+    }
 }
@@ -0,0 +1,73 @@
+use alloc::string::String;
+use rug::Integer;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{bigint::to_be_bytes, errors::ErrorKind, field::Field};
+
+/// Barreto-Naehrig curve BN254 (alt_bn128)
+pub mod bn254;
+
+/// Barreto-Lynn-Scott curve BLS12-381
+pub mod bls12_381;
+
+/// Curve descriptor, integers are written in decimal or in hexadecimal with a 0x prefix
+// The coefficients are (a1, a2, a3, a4, a6) in y² + a1 xy + a3 y = x³ + a2 x² + a4 x + a6 over
+// the prime field of order base_prime, and the generator is a point of order group_order
+// See EllipticCurve::from_params
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CurveParams {
+    pub base_prime: String,
+    pub coefficients: [String; 5],
+    pub group_order: String,
+    pub cofactor: String,
+    pub embedding_degree: u32,
+    pub generator: (String, String),
+}
+
+impl CurveParams {
+    /// Base field prime
+    pub fn base_prime(&self) -> Result<Integer, ErrorKind> {
+        parse(&self.base_prime)
+    }
+
+    /// Order of the subgroup generated by the generator
+    pub fn group_order(&self) -> Result<Integer, ErrorKind> {
+        parse(&self.group_order)
+    }
+
+    /// Cofactor, the number of points divided by the group order
+    pub fn cofactor(&self) -> Result<Integer, ErrorKind> {
+        parse(&self.cofactor)
+    }
+}
+
+// Parses a decimal or 0x-prefixed hexadecimal integer
+fn parse(s: &str) -> Result<Integer, ErrorKind> {
+    let (digits, radix) = match s.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (s, 10),
+    };
+    Integer::from_str_radix(digits, radix).map_err(|_| ErrorKind::InvalidInput("Invalid integer"))
+}
+
+// Parses an integer and maps it to the prime subfield of F, whose characteristic is p
+pub(crate) fn parse_element<F: Field>(s: &str, p: &Integer) -> Result<F, ErrorKind> {
+    Ok(element(&parse(s)?.modulo(p)))
+}
+
+// Parses a hexadecimal constant
+fn from_hex(s: &str) -> Integer {
+    Integer::from_str_radix(s, 16).expect("invalid hexadecimal constant")
+}
+
+// Maps a non-negative integer to the prime subfield of F
+pub(crate) fn element<F: Field>(n: &Integer) -> F {
+    to_be_bytes(n, n.significant_digits::<u8>())
+        .iter()
+        .fold(F::zero(), |acc, b| {
+            acc.zmul(256).add(&F::one().zmul(*b as i64))
+        })
+}
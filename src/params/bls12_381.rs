@@ -0,0 +1,190 @@
+use alloc::string::{String, ToString};
+use rug::Integer;
+
+use super::{from_hex, CurveParams};
+use crate::{
+    elliptic_curve::{ECPoint, EllipticCurve},
+    errors::ErrorKind,
+    field::Field,
+};
+
+// BLS12-381 parameters
+// The curve is y² = x³ + 4 over Fp, with p = (x - 1)²(x⁴ - x² + 1)/3 + x
+// G2 lives on the sextic twist y² = x³ + 4(1 + i) over Fp2 = Fp[i]/(i² + 1)
+
+/// Embedding degree
+pub const EMBEDDING_DEGREE: u32 = 12;
+
+/// BLS parameter x, the loop count of optimal_ate_pairing with CurveFamily::Bls, also used by
+/// the final exponentiation
+pub fn x() -> Integer {
+    -from_hex("d201000000010000")
+}
+
+/// Base field prime p = (x - 1)²(x⁴ - x² + 1)/3 + x
+pub fn base_prime() -> Integer {
+    let x = x();
+    let xm1 = (x.clone() - 1u32).square();
+    xm1 * group_order() / 3u32 + x
+}
+
+/// Order of G1 and G2, r = x⁴ - x² + 1
+pub fn group_order() -> Integer {
+    let x2 = x().square();
+    x2.clone().square() - x2 + 1u32
+}
+
+/// Cofactor of G1, h = (x - 1)²/3
+pub fn cofactor() -> Integer {
+    (x() - 1u32).square() / 3u32
+}
+
+/// Trace of the Frobenius over Fp, t = x + 1
+pub fn trace() -> Integer {
+    x() + 1u32
+}
+
+/// Descriptor of the curve y² = x³ + 4 and of the standard generator of G1
+pub fn params() -> CurveParams {
+    CurveParams {
+        base_prime: base_prime().to_string(),
+        coefficients: ["0", "0", "0", "0", "4"].map(String::from),
+        group_order: group_order().to_string(),
+        cofactor: cofactor().to_string(),
+        embedding_degree: EMBEDDING_DEGREE,
+        generator: (
+            "0x17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb".into(),
+            "0x08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1".into(),
+        ),
+    }
+}
+
+/// The curve y² = x³ + 4, with the order and cofactor of G1 set
+/// Fails unless F has characteristic p, see EllipticCurve::from_params
+pub fn curve<F: Field + Clone + PartialEq>() -> Result<EllipticCurve<F>, ErrorKind> {
+    Ok(EllipticCurve::from_params(&params())?.0)
+}
+
+/// Generator of G1
+/// Fails unless F has characteristic p, see EllipticCurve::from_params
+pub fn g1_generator<F: Field + Clone + PartialEq>() -> Result<ECPoint<F>, ErrorKind> {
+    Ok(EllipticCurve::from_params(&params())?.1)
+}
+
+/// Generator of G2 on the twist, as ((x0, x1), (y0, y1)) where x = x0 + x1 i and y = y0 + y1 i
+pub fn g2_generator() -> ((Integer, Integer), (Integer, Integer)) {
+    (
+        (
+            from_hex("024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"),
+            from_hex("13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"),
+        ),
+        (
+            from_hex("0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"),
+            from_hex("0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pairings::{optimal_ate_pairing, CurveFamily, Gt},
+        test_fields::{Bls12381Fp12, Fp, Fp12},
+        twist::Twist,
+    };
+
+    type K = Fp12<Bls12381Fp12>;
+
+    // Generator of G2, untwisted into E(Fq12)
+    // The twist is of M-type, y² = x³ + 4ξ, so that the twisting element is 1/w
+    fn g2() -> ECPoint<K> {
+        let ((x0, x1), (y0, y1)) = g2_generator();
+        let pt = ECPoint::AffinePoint(K::from_fp2(&x0, &x1), K::from_fp2(&y0, &y1));
+        Twist::new(6, K::w().invert())
+            .unwrap()
+            .untwist(&pt, K::clone)
+    }
+
+    fn pair(pt_p: &ECPoint<K>, pt_q: &ECPoint<K>) -> Gt<K> {
+        optimal_ate_pairing(
+            &curve().unwrap(),
+            pt_p,
+            pt_q,
+            &x(),
+            CurveFamily::Bls,
+            EMBEDDING_DEGREE,
+            &group_order(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            base_prime(),
+            from_hex("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab")
+        );
+        assert_eq!(
+            group_order(),
+            from_hex("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001")
+        );
+        assert_eq!(cofactor(), from_hex("396c8c005555e1568c00aaab0000aaab"));
+        assert_eq!(base_prime() + 1u32 - trace(), group_order() * cofactor());
+    }
+
+    #[test]
+    fn curve_needs_the_base_prime() {
+        assert!(curve::<Fp<103>>().is_err());
+        assert!(g1_generator::<Fp<103>>().is_err());
+        let curve = curve::<K>().unwrap();
+        assert_eq!(curve.order(), Some(&group_order()));
+        assert_eq!(curve.cofactor(), Some(&cofactor()));
+    }
+
+    #[test]
+    fn g2_is_in_the_subgroup() {
+        let curve = curve::<K>().unwrap();
+        let pt_q = g2();
+        assert!(curve.is_on_curve(&pt_q));
+        assert!(curve.is_in_subgroup(&pt_q).unwrap());
+    }
+
+    #[test]
+    fn optimal_ate_is_bilinear() {
+        let curve = curve::<K>().unwrap();
+        let (pt_p, pt_q) = (g1_generator().unwrap(), g2());
+        let e = pair(&pt_p, &pt_q);
+        assert!(!e.is_one());
+        assert!(e.clone().pow(&group_order()).is_one());
+
+        let (a, b) = (Integer::from(6), Integer::from(35));
+        let lhs = pair(
+            &curve.mul(&a, &pt_p).unwrap(),
+            &curve.mul(&b, &pt_q).unwrap(),
+        );
+        assert_eq!(lhs, e.clone().pow(&(a * b)));
+    }
+
+    // e(G1, G2) in the basis 1, w, .., w¹¹ of Fq12 = Fq[w]/(w¹² - 2w⁶ + 2)
+    #[test]
+    fn optimal_ate_value() {
+        let expected = [
+            "1625cbe5b8f9885da3eccb3b15ceb7646a1565fe42582504e54b29c30019f6b06bcb8385a3243d0c1ba15dea3c023184",
+            "136507b6e1cbec80530db4a99e8a11fb3b33193142b57ba3ff0bde78ea471317d367bbba605c007dc1c8de294b5c3bf8",
+            "c788d3b1b51c02ee78fe6cc41bfaeb58946e0fc615b5f493f9521028e781165dc7888126296311e6a8cbc7e6af205de",
+            "187c9a241b75af510864cc7df3090e28e6b917e4b4bf544ac8900deb0835fbb9008f02f167f90a87556cffcc97fd58dd",
+            "12b9dce6cfccf7c3c4f6cdca4518b20e428ead36196401a7c3211459685fc93f8bebff732cdf0943612265c79ce3e12c",
+            "163c93d3b228c66865eb71b29704a20807d2c349174f801c8d0f3a43d6277112313f87e8fe422867c27854bb14d035e6",
+            "153ce14a76a53e205ba8f275ef1137c56a566f638b52d34ba3bf3bf22f277d70f76316218c0dfd583a394b8448d2be7f",
+            "8890726743a1f94a8193a166800b7787744a8ad8e2f9365db76863e894b7a11d83f90d873567e9d645ccf725b32d26f",
+            "16deedaa683124fe7260085184d88f7d036b86f53bb5b7f1fc5e248814782065413e7d958d17960109ea006b2afdeb5f",
+            "fe63f185f56dd29150fc498bbeea78969e7e783043620db33f75a05a0a2ce5c442beaff9da195ff15164c00ab66bdde",
+            "111061f398efc2a97ff825b04d21089e24fd8b93a47e41e60eae7e9b2a38d54fa4dedced0811c34ce528781ab9e929c7",
+            "1454814f3085f0e6602247671bc408bbce2007201536818c901dbd4d2095dd86c1ec8b888e59611f60a301af7776be3d",
+        ]
+        .map(from_hex);
+        let e = pair(&g1_generator().unwrap(), &g2());
+        assert_eq!(e.value().coeffs(), expected);
+    }
+}
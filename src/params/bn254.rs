@@ -0,0 +1,185 @@
+use alloc::string::{String, ToString};
+use rug::Integer;
+
+use super::{from_hex, CurveParams};
+use crate::{
+    elliptic_curve::{ECPoint, EllipticCurve},
+    errors::ErrorKind,
+    field::Field,
+};
+
+// BN254 parameters
+// The curve is y² = x³ + 3 over Fp, with p = 36u⁴ + 36u³ + 24u² + 6u + 1
+// G2 lives on the sextic twist y² = x³ + 3/(9 + i) over Fp2 = Fp[i]/(i² + 1)
+
+/// Embedding degree
+pub const EMBEDDING_DEGREE: u32 = 12;
+
+/// BN parameter u
+pub fn u() -> Integer {
+    from_hex("44e992b44a6909f1")
+}
+
+/// Base field prime p = 36u⁴ + 36u³ + 24u² + 6u + 1
+pub fn base_prime() -> Integer {
+    let u = u();
+    let u2 = u.clone().square();
+    (u2.clone().square() * 36u32) + (u2.clone() * &u * 36u32) + (u2 * 24u32) + (u * 6u32) + 1u32
+}
+
+/// Order of G1 and G2, r = 36u⁴ + 36u³ + 18u² + 6u + 1
+pub fn group_order() -> Integer {
+    let u = u();
+    let u2 = u.clone().square();
+    (u2.clone().square() * 36u32) + (u2.clone() * &u * 36u32) + (u2 * 18u32) + (u * 6u32) + 1u32
+}
+
+/// Trace of the Frobenius over Fp, t = 6u² + 1
+pub fn trace() -> Integer {
+    u().square() * 6u32 + 1u32
+}
+
+/// Loop count 6u + 2 of optimal_ate_pairing, with CurveFamily::Bn
+pub fn ate_loop_count() -> Integer {
+    u() * 6u32 + 2u32
+}
+
+/// Descriptor of the curve y² = x³ + 3 and of the generator (1, 2) of G1
+pub fn params() -> CurveParams {
+    CurveParams {
+        base_prime: base_prime().to_string(),
+        coefficients: ["0", "0", "0", "0", "3"].map(String::from),
+        group_order: group_order().to_string(),
+        cofactor: "1".into(),
+        embedding_degree: EMBEDDING_DEGREE,
+        generator: ("1".into(), "2".into()),
+    }
+}
+
+/// The curve y² = x³ + 3, with the order and cofactor of G1 set
+/// Fails unless F has characteristic p, see EllipticCurve::from_params
+pub fn curve<F: Field + Clone + PartialEq>() -> Result<EllipticCurve<F>, ErrorKind> {
+    Ok(EllipticCurve::from_params(&params())?.0)
+}
+
+/// Generator of G1, (1, 2)
+/// Fails unless F has characteristic p, see EllipticCurve::from_params
+pub fn g1_generator<F: Field + Clone + PartialEq>() -> Result<ECPoint<F>, ErrorKind> {
+    Ok(EllipticCurve::from_params(&params())?.1)
+}
+
+/// Generator of G2 on the twist, as ((x0, x1), (y0, y1)) where x = x0 + x1 i and y = y0 + y1 i
+pub fn g2_generator() -> ((Integer, Integer), (Integer, Integer)) {
+    (
+        (
+            from_hex("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"),
+            from_hex("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"),
+        ),
+        (
+            from_hex("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"),
+            from_hex("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pairings::{optimal_ate_pairing, CurveFamily, Gt},
+        test_fields::{Bn254Fp12, Fp, Fp12},
+        twist::Twist,
+    };
+
+    type K = Fp12<Bn254Fp12>;
+
+    // Generator of G2, untwisted into E(Fq12)
+    fn g2() -> ECPoint<K> {
+        let ((x0, x1), (y0, y1)) = g2_generator();
+        let pt = ECPoint::AffinePoint(K::from_fp2(&x0, &x1), K::from_fp2(&y0, &y1));
+        Twist::new(6, K::w()).unwrap().untwist(&pt, K::clone)
+    }
+
+    fn pair(pt_p: &ECPoint<K>, pt_q: &ECPoint<K>) -> Gt<K> {
+        let r = group_order();
+        optimal_ate_pairing(
+            &curve().unwrap(),
+            pt_p,
+            pt_q,
+            &ate_loop_count(),
+            CurveFamily::Bn,
+            EMBEDDING_DEGREE,
+            &r,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            base_prime().to_string(),
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583"
+        );
+        assert_eq!(
+            group_order().to_string(),
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+        );
+        assert_eq!(ate_loop_count().to_string(), "29793968203157093288");
+        assert_eq!(base_prime() + 1u32 - trace(), group_order());
+    }
+
+    #[test]
+    fn curve_needs_the_base_prime() {
+        assert!(curve::<Fp<103>>().is_err());
+        assert!(g1_generator::<Fp<103>>().is_err());
+        let curve = curve::<K>().unwrap();
+        assert_eq!(curve.order(), Some(&group_order()));
+        assert_eq!(curve.cofactor(), Some(&Integer::from(1)));
+    }
+
+    #[test]
+    fn g2_is_in_the_subgroup() {
+        let curve = curve::<K>().unwrap();
+        let pt_q = g2();
+        assert!(curve.is_on_curve(&pt_q));
+        assert!(curve.is_in_subgroup(&pt_q).unwrap());
+    }
+
+    #[test]
+    fn optimal_ate_is_bilinear() {
+        let curve = curve::<K>().unwrap();
+        let (pt_p, pt_q) = (g1_generator().unwrap(), g2());
+        let e = pair(&pt_p, &pt_q);
+        assert!(!e.is_one());
+        assert!(e.clone().pow(&group_order()).is_one());
+
+        let (a, b) = (Integer::from(6), Integer::from(35));
+        let lhs = pair(
+            &curve.mul(&a, &pt_p).unwrap(),
+            &curve.mul(&b, &pt_q).unwrap(),
+        );
+        assert_eq!(lhs, e.clone().pow(&(a * b)));
+    }
+
+    // e(G1, G2) in the basis 1, w, .., w¹¹ of Fq12 = Fq[w]/(w¹² - 18w⁶ + 82)
+    #[test]
+    fn optimal_ate_value() {
+        let expected = [
+            "28c6e04df059260df7d2d2a1f9b5f77676d1939847852c4ed50d2318744c1d5f",
+            "17bb74adab1705c26133af1dac87044a3833ac011018e8158da48382bbd2dcd6",
+            "d3bd72f54d742f78ea9e6015c8ea2f2e7fbb728c9c905ec531dcf7de5b246f0",
+            "90cb8ee97e091a667af03882b06c3ecb4e437993cbd1b05b98c7f9dfcfe9c40",
+            "16b6d855b5cbf76f9829a309db52f5c442f65ae29f996af59d65f85f4afe78a",
+            "a0272204db51dadc0342bd318b9302a44faec12ff500bdd4d4b012ffe45f36f",
+            "84f330485b09e866bc2f2ea2b897394deaf3f12aa31f28cb0552990967d4704",
+            "27ed208e7a0b55ae6e710bbfbd2fd922669c026360e37cc5b2ab862411536104",
+            "2067586885c3318eeffa1938c754fe3c60224ee5ae15e66af6b5104c47c8c5d8",
+            "279db296f9d479292532c7c493d8e0722b6efae42158387564889c79fc038ee3",
+            "2b03614464f04dd772d86df88674c270ffc8747ea13e72da95e3594468f222c4",
+            "108c19d15f9446f744d0f110405d3856d6cc3bda6c4d537663729f5257628417",
+        ]
+        .map(from_hex);
+        let e = pair(&g1_generator().unwrap(), &g2());
+        assert_eq!(e.value().coeffs(), expected);
+    }
+}
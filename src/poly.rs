@@ -0,0 +1,223 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{errors::ErrorKind, field::Field};
+
+// Polynomial over a field
+// Little endian coefficients, without trailing zeros: the zero polynomial has none
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial<F: Field> {
+    coeffs: Vec<F>,
+}
+
+impl<F: Field + Clone + PartialEq> Polynomial<F> {
+    // New polynomial from its coefficients, constant term first
+    pub fn new(coeffs: Vec<F>) -> Self {
+        let mut poly = Polynomial { coeffs };
+        poly.normalize();
+        poly
+    }
+
+    // Zero polynomial
+    pub fn zero() -> Self {
+        Polynomial { coeffs: vec![] }
+    }
+
+    // Constant polynomial
+    pub fn constant(c: F) -> Self {
+        Self::new(vec![c])
+    }
+
+    // The polynomial x
+    pub fn x() -> Self {
+        Self::new(vec![F::zero(), F::one()])
+    }
+
+    // Coefficients, constant term first
+    pub fn coefficients(&self) -> &[F] {
+        &self.coeffs
+    }
+
+    // Degree, None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    // Whether the polynomial is zero
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    // Addition
+    pub fn add(&self, y: &Self) -> Self {
+        let (long, short) = if self.coeffs.len() >= y.coeffs.len() {
+            (self, y)
+        } else {
+            (y, self)
+        };
+        let mut coeffs = long.coeffs.clone();
+        for (c, d) in coeffs.iter_mut().zip(&short.coeffs) {
+            *c = c.clone().add(d);
+        }
+        Self::new(coeffs)
+    }
+
+    // Additive inverse
+    pub fn neg(&self) -> Self {
+        Polynomial {
+            coeffs: self.coeffs.iter().map(|c| c.clone().neg()).collect(),
+        }
+    }
+
+    // Subtraction
+    pub fn sub(&self, y: &Self) -> Self {
+        self.add(&y.neg())
+    }
+
+    // Multiplication
+    // Schoolbook product, in O(deg(self) deg(y))
+    pub fn mul(&self, y: &Self) -> Self {
+        if self.is_zero() || y.is_zero() {
+            return Self::zero();
+        }
+        let mut coeffs = vec![F::zero(); self.coeffs.len() + y.coeffs.len() - 1];
+        for (i, c) in self.coeffs.iter().enumerate() {
+            for (j, d) in y.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j].clone().add(&c.clone().mul(d));
+            }
+        }
+        Self::new(coeffs)
+    }
+
+    // Multiplication by a field element
+    pub fn scale(&self, c: &F) -> Self {
+        Self::new(self.coeffs.iter().map(|d| d.clone().mul(c)).collect())
+    }
+
+    // Squaring
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    // Euclidean division, returns (q, r) with self = q y + r and deg(r) < deg(y)
+    // Fails if y is zero
+    pub fn div_rem(&self, y: &Self) -> Result<(Self, Self), ErrorKind> {
+        let (lead, dy) = match (y.coeffs.last(), y.degree()) {
+            (Some(lead), Some(dy)) => (lead.clone().invert(), dy),
+            _ => return Err(ErrorKind::InvalidInput("Division by the zero polynomial")),
+        };
+
+        let mut r = self.coeffs.clone();
+        let mut q = vec![F::zero(); r.len().saturating_sub(dy)];
+        // Cancels the leading coefficient of the remainder, from the top
+        for i in (dy..r.len()).rev() {
+            let c = r[i].clone().mul(&lead);
+            for (j, d) in y.coeffs.iter().enumerate() {
+                r[i - dy + j] = r[i - dy + j].clone().add(&c.clone().mul(d).neg());
+            }
+            q[i - dy] = c;
+        }
+        r.truncate(dy);
+        Ok((Self::new(q), Self::new(r)))
+    }
+
+    // Remainder of the Euclidean division by y, e.g. the reduction modulo the polynomial
+    // defining an extension field
+    // Fails if y is zero
+    pub fn rem(&self, y: &Self) -> Result<Self, ErrorKind> {
+        Ok(self.div_rem(y)?.1)
+    }
+
+    // Evaluation at x, with Horner's rule
+    pub fn evaluate(&self, x: &F) -> F {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, c| acc.mul(x).add(c))
+    }
+
+    // Drops the trailing zero coefficients
+    fn normalize(&mut self) {
+        while self.coeffs.last().is_some_and(|c| *c == F::zero()) {
+            self.coeffs.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fields::Fp;
+
+    type F = Fp<103>;
+
+    fn poly(coeffs: &[i64]) -> Polynomial<F> {
+        Polynomial::new(coeffs.iter().map(|c| F::new(*c)).collect())
+    }
+
+    #[test]
+    fn new_drops_trailing_zeros() {
+        assert_eq!(poly(&[1, 2, 0, 103]), poly(&[1, 2]));
+        assert_eq!(poly(&[1, 2, 0]).degree(), Some(1));
+        assert_eq!(poly(&[0, 0]), Polynomial::zero());
+        assert_eq!(Polynomial::<F>::zero().degree(), None);
+        assert_eq!(poly(&[1, 1]).sub(&poly(&[0, 1])), poly(&[1]));
+        assert!(poly(&[1, 1]).sub(&poly(&[1, 1])).is_zero());
+    }
+
+    #[test]
+    fn mul_and_evaluate() {
+        // (x + 1)(x - 1) = x² - 1
+        let p = poly(&[1, 1]).mul(&poly(&[-1, 1]));
+        assert_eq!(p, poly(&[-1, 0, 1]));
+        assert_eq!(p.coefficients(), [F::new(-1), F::zero(), F::one()]);
+        assert_eq!(poly(&[1, 1]).square(), poly(&[1, 2, 1]));
+        assert!(p.mul(&Polynomial::zero()).is_zero());
+        assert_eq!(p.scale(&F::new(2)), poly(&[-2, 0, 2]));
+        for x in [0, 1, 5, 102] {
+            let x = F::new(x);
+            assert_eq!(p.evaluate(&x), x.clone().square().add(&F::one().neg()));
+        }
+        assert_eq!(Polynomial::<F>::zero().evaluate(&F::new(5)), F::zero());
+    }
+
+    #[test]
+    fn div_rem_and_rem() {
+        // x³ + 2x + 3 = (x + 1)(x² - x + 3) + 0, and = x (x² + 2) + 3
+        let a = poly(&[3, 2, 0, 1]);
+        assert_eq!(
+            a.div_rem(&poly(&[1, 1])),
+            Ok((poly(&[3, -1, 1]), Polynomial::zero()))
+        );
+        assert_eq!(
+            a.div_rem(&poly(&[0, 1])),
+            Ok((poly(&[2, 0, 1]), poly(&[3])))
+        );
+        assert_eq!(a.rem(&poly(&[0, 0, 2])), Ok(poly(&[3, 2])));
+
+        // self = q y + r with deg(r) < deg(y)
+        let y = poly(&[5, 7, 11]);
+        let (q, r) = a.div_rem(&y).unwrap();
+        assert_eq!(q.mul(&y).add(&r), a);
+        assert!(r.degree() < y.degree());
+
+        // Dividing by a polynomial of larger degree leaves self as the remainder
+        assert_eq!(y.div_rem(&a), Ok((Polynomial::zero(), y.clone())));
+        // By a constant
+        assert_eq!(
+            a.div_rem(&poly(&[2])),
+            Ok((a.scale(&F::new(2).invert()), Polynomial::zero()))
+        );
+    }
+
+    #[test]
+    fn division_by_zero_polynomial() {
+        let err = Err(ErrorKind::InvalidInput("Division by the zero polynomial"));
+        assert_eq!(poly(&[1, 2]).div_rem(&Polynomial::zero()), err);
+        assert_eq!(poly(&[1, 2]).rem(&Polynomial::zero()), err.map(|(_, r)| r));
+        assert!(Polynomial::<F>::zero().rem(&poly(&[0, 0])).is_err());
+        assert_eq!(
+            Polynomial::<F>::zero().div_rem(&poly(&[1, 1])),
+            Ok((Polynomial::zero(), Polynomial::zero()))
+        );
+    }
+}
@@ -0,0 +1,700 @@
+// Fields used by the unit tests, the crate itself only defines the Field trait
+extern crate std;
+
+use alloc::{vec, vec::Vec};
+use core::{
+    cell::Cell,
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use rug::{integer::Order, Integer};
+use std::sync::OnceLock;
+
+use crate::{
+    bigint::to_be_bytes,
+    field::{square_root, Field, FieldBytes},
+    poly::Polynomial,
+};
+
+static SEED: AtomicU64 = AtomicU64::new(0x9e37_79b9_7f4a_7c15);
+
+// Xorshift generator, deterministic so that test failures can be reproduced
+pub(crate) fn rnd() -> u64 {
+    let mut x = SEED.load(Ordering::Relaxed);
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    SEED.store(x, Ordering::Relaxed);
+    x
+}
+
+// Modular power on machine integers
+fn pow_mod(b: u64, e: u64, p: u64) -> u64 {
+    let (mut b, mut e, p) = (b as u128 % p as u128, e, p as u128);
+    let mut r = 1 % p;
+    while e > 0 {
+        if e & 1 == 1 {
+            r = r * b % p;
+        }
+        b = b * b % p;
+        e >>= 1;
+    }
+    r as u64
+}
+
+// Prime field Fp for a small prime P
+// The value may be unreduced, e.g. Fp(106) for P = 103: comparisons and hashing use the
+// canonical representative in [0, P)
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Fp<const P: u64>(pub(crate) u64);
+
+impl<const P: u64> Fp<P> {
+    pub(crate) fn new(v: i64) -> Self {
+        Fp(v.rem_euclid(P as i64) as u64)
+    }
+
+    fn canonical(&self) -> u64 {
+        self.0 % P
+    }
+}
+
+impl<const P: u64> PartialEq for Fp<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl<const P: u64> Eq for Fp<P> {}
+
+impl<const P: u64> Hash for Fp<P> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state);
+    }
+}
+
+impl<const P: u64> PartialOrd for Fp<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const P: u64> Ord for Fp<P> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.canonical().cmp(&other.canonical())
+    }
+}
+
+impl<const P: u64> Field for Fp<P> {
+    fn zero() -> Self {
+        Fp(0)
+    }
+
+    fn one() -> Self {
+        Fp(1 % P)
+    }
+
+    fn ct_eq(&self, y: &Self) -> bool {
+        (self.canonical() ^ y.canonical()) == 0
+    }
+
+    fn add(self, y: &Self) -> Self {
+        Fp(((self.0 as u128 + y.0 as u128) % P as u128) as u64)
+    }
+
+    fn mul(self, y: &Self) -> Self {
+        Fp(((self.0 as u128 * y.0 as u128) % P as u128) as u64)
+    }
+
+    fn zmul(self, y: i64) -> Self {
+        self.mul(&Fp::new(y))
+    }
+
+    fn pow(self, y: &Integer) -> Self {
+        let e = y.clone().modulo(&Integer::from(P - 1));
+        Fp(pow_mod(self.0, e.to_u64().unwrap_or(0), P))
+    }
+
+    fn zpow(self, y: i64) -> Self {
+        self.pow(&Integer::from(y))
+    }
+
+    fn div(self, y: &Self) -> Self {
+        self.mul(&y.clone().invert())
+    }
+
+    fn square(self) -> Self {
+        let y = self.clone();
+        self.mul(&y)
+    }
+
+    fn sqrt(self) -> Self {
+        square_root(&self, &Self::order()).unwrap_or(Fp(0))
+    }
+
+    fn invert(self) -> Self {
+        Fp(pow_mod(self.0, P - 2, P))
+    }
+
+    fn neg(self) -> Self {
+        Fp((P - self.0 % P) % P)
+    }
+
+    fn degree() -> u32 {
+        1
+    }
+
+    fn base_order() -> Integer {
+        Integer::from(P)
+    }
+
+    fn characteristic() -> Integer {
+        Integer::from(P)
+    }
+
+    fn random_element() -> Self {
+        Fp(rnd() % P)
+    }
+}
+
+impl<const P: u64> FieldBytes for Fp<P> {
+    fn byte_len() -> usize {
+        8
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.canonical().to_be_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let v = u64::from_be_bytes(bytes.try_into().ok()?);
+        (v < P).then_some(Fp(v))
+    }
+}
+
+// Quadratic extension Fp2 = Fp[i]/(i² + 1), for P = 3 mod 4
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Fp2<const P: u64>(pub(crate) Fp<P>, pub(crate) Fp<P>);
+
+impl<const P: u64> Fp2<P> {
+    pub(crate) fn new(a: i64, b: i64) -> Self {
+        Fp2(Fp::new(a), Fp::new(b))
+    }
+}
+
+impl<const P: u64> Field for Fp2<P> {
+    fn zero() -> Self {
+        Fp2(Fp::zero(), Fp::zero())
+    }
+
+    fn one() -> Self {
+        Fp2(Fp::one(), Fp::zero())
+    }
+
+    fn ct_eq(&self, y: &Self) -> bool {
+        self.0.ct_eq(&y.0) & self.1.ct_eq(&y.1)
+    }
+
+    fn add(self, y: &Self) -> Self {
+        Fp2(self.0.add(&y.0), self.1.add(&y.1))
+    }
+
+    // (a + bi)(c + di) = ac - bd + (ad + bc) i
+    fn mul(self, y: &Self) -> Self {
+        let re = self
+            .0
+            .clone()
+            .mul(&y.0)
+            .add(&self.1.clone().mul(&y.1).neg());
+        let im = self.0.mul(&y.1).add(&self.1.mul(&y.0));
+        Fp2(re, im)
+    }
+
+    fn zmul(self, y: i64) -> Self {
+        Fp2(self.0.zmul(y), self.1.zmul(y))
+    }
+
+    fn pow(self, y: &Integer) -> Self {
+        let e = y.clone().modulo(&(Self::order() - 1u32));
+        self.pow_bits(
+            &(0..e.significant_bits())
+                .rev()
+                .map(|i| e.get_bit(i))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn zpow(self, y: i64) -> Self {
+        self.pow(&Integer::from(y))
+    }
+
+    fn div(self, y: &Self) -> Self {
+        self.mul(&y.clone().invert())
+    }
+
+    fn square(self) -> Self {
+        let y = self.clone();
+        self.mul(&y)
+    }
+
+    fn sqrt(self) -> Self {
+        square_root(&self, &Self::order()).unwrap_or_else(|_| Self::zero())
+    }
+
+    // 1 / (a + bi) = (a - bi) / (a² + b²)
+    fn invert(self) -> Self {
+        let norm = self
+            .0
+            .clone()
+            .square()
+            .add(&self.1.clone().square())
+            .invert();
+        Fp2(self.0.mul(&norm), self.1.neg().mul(&norm))
+    }
+
+    fn neg(self) -> Self {
+        Fp2(self.0.neg(), self.1.neg())
+    }
+
+    // Conjugation, as i^P = -i
+    fn frobenius(&self) -> Self {
+        Fp2(self.0.clone(), self.1.clone().neg())
+    }
+
+    fn degree() -> u32 {
+        2
+    }
+
+    fn base_order() -> Integer {
+        Integer::from(P)
+    }
+
+    fn characteristic() -> Integer {
+        Integer::from(P)
+    }
+
+    fn random_element() -> Self {
+        Fp2(Fp::random_element(), Fp::random_element())
+    }
+}
+
+// Modulus of a big prime field
+pub(crate) trait Modulus: Clone + Debug + PartialEq + Eq + Hash {
+    fn modulus() -> &'static Integer;
+}
+
+macro_rules! modulus {
+    ($name:ident, $p:expr) => {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub(crate) struct $name;
+
+        impl Modulus for $name {
+            fn modulus() -> &'static Integer {
+                static P: OnceLock<Integer> = OnceLock::new();
+                P.get_or_init(|| $p)
+            }
+        }
+    };
+}
+
+modulus!(Bn254, crate::params::bn254::base_prime());
+modulus!(Bls12381, crate::params::bls12_381::base_prime());
+modulus!(
+    P256,
+    Integer::from_str_radix(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+        16
+    )
+    .unwrap()
+);
+
+// Prime field Fq for a big prime given by M
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct Fq<M: Modulus>(Integer, PhantomData<M>);
+
+impl<M: Modulus> Fq<M> {
+    pub(crate) fn new(v: Integer) -> Self {
+        Fq(v.modulo(M::modulus()), PhantomData)
+    }
+}
+
+impl<M: Modulus> Field for Fq<M> {
+    fn zero() -> Self {
+        Fq::new(Integer::new())
+    }
+
+    fn one() -> Self {
+        Fq::new(Integer::from(1))
+    }
+
+    fn ct_eq(&self, y: &Self) -> bool {
+        self.0 == y.0
+    }
+
+    fn add(self, y: &Self) -> Self {
+        Fq::new(self.0 + &y.0)
+    }
+
+    fn mul(self, y: &Self) -> Self {
+        Fq::new(self.0 * &y.0)
+    }
+
+    fn zmul(self, y: i64) -> Self {
+        Fq::new(self.0 * y)
+    }
+
+    fn pow(self, y: &Integer) -> Self {
+        Fq::new(self.0.pow_mod(y, M::modulus()).unwrap_or_default())
+    }
+
+    fn zpow(self, y: i64) -> Self {
+        self.pow(&Integer::from(y))
+    }
+
+    fn div(self, y: &Self) -> Self {
+        self.mul(&y.clone().invert())
+    }
+
+    fn square(self) -> Self {
+        Fq::new(self.0.square())
+    }
+
+    fn sqrt(self) -> Self {
+        square_root(&self, M::modulus()).unwrap_or_else(|_| Self::zero())
+    }
+
+    fn invert(self) -> Self {
+        Fq::new(self.0.invert(M::modulus()).unwrap_or_default())
+    }
+
+    fn neg(self) -> Self {
+        Fq::new(-self.0)
+    }
+
+    fn degree() -> u32 {
+        1
+    }
+
+    fn base_order() -> Integer {
+        M::modulus().clone()
+    }
+
+    fn characteristic() -> Integer {
+        M::modulus().clone()
+    }
+
+    fn random_element() -> Self {
+        let limbs: Vec<u64> = (0..8).map(|_| rnd()).collect();
+        Fq::new(Integer::from_digits(&limbs, Order::Lsf))
+    }
+}
+
+impl<M: Modulus> FieldBytes for Fq<M> {
+    fn byte_len() -> usize {
+        M::modulus().significant_bits().div_ceil(8) as usize
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        to_be_bytes(&self.0, Self::byte_len())
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let v = Integer::from_digits(bytes, Order::Msf);
+        (bytes.len() == Self::byte_len() && v < *M::modulus()).then(|| Fq(v, PhantomData))
+    }
+}
+
+// Degree 12 extension of Fq, for the pairings of BN and BLS12 curves
+// Fq12 = Fq[w]/(w¹² - 2a w⁶ + a² + 1), so that w⁶ = ξ = a + i with i = w⁶ - a a square root
+// of -1: Fq12 contains Fq2 = Fq[i]/(i² + 1), and the sextic twists of the curves are defined
+// with ξ over Fq2
+pub(crate) trait Fp12Params:
+    Clone + Debug + PartialEq + Eq + Hash + Sized + 'static
+{
+    type Base: Modulus;
+
+    // Real part a of ξ
+    const XI: i64;
+
+    // γj = w^(jq), for the Frobenius
+    fn frobenius_basis() -> &'static [Fp12<Self>];
+}
+
+macro_rules! fp12_params {
+    ($name:ident, $base:ident, $xi:expr) => {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub(crate) struct $name;
+
+        impl Fp12Params for $name {
+            type Base = $base;
+            const XI: i64 = $xi;
+
+            fn frobenius_basis() -> &'static [Fp12<Self>] {
+                static GAMMA: OnceLock<Vec<Fp12<$name>>> = OnceLock::new();
+                GAMMA.get_or_init(|| {
+                    let w_q = Fp12::w().pow($base::modulus());
+                    core::iter::successors(Some(Fp12::one()), |g| Some(g.clone().mul(&w_q)))
+                        .take(12)
+                        .collect()
+                })
+            }
+        }
+    };
+}
+
+fp12_params!(Bn254Fp12, Bn254, 9);
+fp12_params!(Bls12381Fp12, Bls12381, 1);
+
+// Element of Fq12, by its 12 coefficients in the basis 1, w, .., w¹¹
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct Fp12<C: Fp12Params>(Vec<Integer>, PhantomData<C>);
+
+impl<C: Fp12Params> Fp12<C> {
+    fn new(mut coeffs: Vec<Integer>) -> Self {
+        coeffs.resize(12, Integer::new());
+        for c in coeffs.iter_mut() {
+            c.modulo_mut(C::Base::modulus());
+        }
+        Fp12(coeffs, PhantomData)
+    }
+
+    // Element of the prime field
+    pub(crate) fn from_base(v: &Integer) -> Self {
+        Fp12::new(vec![v.clone()])
+    }
+
+    // Element v0 + v1 i of Fq2, with i = w⁶ - a
+    pub(crate) fn from_fp2(v0: &Integer, v1: &Integer) -> Self {
+        let mut coeffs = vec![Integer::new(); 7];
+        coeffs[0] = v0 - (v1 * Integer::from(C::XI));
+        coeffs[6] = v1.clone();
+        Fp12::new(coeffs)
+    }
+
+    // Coefficients in the basis 1, w, .., w¹¹
+    pub(crate) fn coeffs(&self) -> &[Integer] {
+        &self.0
+    }
+
+    // The generator w, with w⁶ = ξ
+    pub(crate) fn w() -> Self {
+        Fp12::new(vec![Integer::new(), Integer::from(1)])
+    }
+
+    // Coefficients as a polynomial in w
+    fn to_polynomial(&self) -> Polynomial<Fq<C::Base>> {
+        Polynomial::new(self.0.iter().map(|c| Fq::new(c.clone())).collect())
+    }
+}
+
+impl<C: Fp12Params> Field for Fp12<C> {
+    fn zero() -> Self {
+        Fp12::new(vec![])
+    }
+
+    fn one() -> Self {
+        Fp12::from_base(&Integer::from(1))
+    }
+
+    fn ct_eq(&self, y: &Self) -> bool {
+        self.0 == y.0
+    }
+
+    fn add(self, y: &Self) -> Self {
+        Fp12::new(self.0.into_iter().zip(&y.0).map(|(a, b)| a + b).collect())
+    }
+
+    // Schoolbook product, then w^k = 2a w^(k-6) - (a² + 1) w^(k-12) for k >= 12
+    fn mul(self, y: &Self) -> Self {
+        let mut t = vec![Integer::new(); 23];
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in y.0.iter().enumerate() {
+                t[i + j] += a * b;
+            }
+        }
+        for k in (12..23).rev() {
+            let top = core::mem::take(&mut t[k]);
+            t[k - 6] += &top * Integer::from(2 * C::XI);
+            t[k - 12] -= top * Integer::from(C::XI * C::XI + 1);
+        }
+        t.truncate(12);
+        Fp12::new(t)
+    }
+
+    fn zmul(self, y: i64) -> Self {
+        Fp12::new(self.0.into_iter().map(|a| a * y).collect())
+    }
+
+    fn pow(self, y: &Integer) -> Self {
+        let base = if *y < 0 { self.invert() } else { self };
+        let bits: Vec<bool> = (0..y.significant_bits())
+            .rev()
+            .map(|i| y.get_bit(i))
+            .collect();
+        base.pow_bits(&bits)
+    }
+
+    fn zpow(self, y: i64) -> Self {
+        self.pow(&Integer::from(y))
+    }
+
+    fn div(self, y: &Self) -> Self {
+        self.mul(&y.clone().invert())
+    }
+
+    fn square(self) -> Self {
+        let y = self.clone();
+        self.mul(&y)
+    }
+
+    fn sqrt(self) -> Self {
+        square_root(&self, &Self::order()).unwrap_or_else(|_| Self::zero())
+    }
+
+    // Extended Euclid in Fq[w]: s x = g mod the defining polynomial, with g constant
+    fn invert(self) -> Self {
+        let mut modulus = vec![Fq::zero(); 13];
+        modulus[0] = Fq::new(Integer::from(C::XI * C::XI + 1));
+        modulus[6] = Fq::new(Integer::from(-2 * C::XI));
+        modulus[12] = Fq::one();
+
+        let (mut r0, mut r1) = (Polynomial::new(modulus), self.to_polynomial());
+        let (mut s0, mut s1) = (Polynomial::zero(), Polynomial::constant(Fq::one()));
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1).unwrap_or_else(|_| unreachable!());
+            let s = s0.sub(&q.mul(&s1));
+            (r0, r1) = (r1, r);
+            (s0, s1) = (s1, s);
+        }
+        match (r0.degree(), r0.coefficients().first()) {
+            (Some(0), Some(g)) => Fp12::new(
+                s0.scale(&g.clone().invert())
+                    .coefficients()
+                    .iter()
+                    .map(|c| c.0.clone())
+                    .collect(),
+            ),
+            _ => Self::zero(),
+        }
+    }
+
+    fn neg(self) -> Self {
+        Fp12::new(self.0.into_iter().map(|a| -a).collect())
+    }
+
+    // (Σ cj w^j)^q = Σ cj γj
+    fn frobenius(&self) -> Self {
+        self.0
+            .iter()
+            .zip(C::frobenius_basis())
+            .fold(Self::zero(), |acc, (c, g)| {
+                acc.add(&g.clone().mul(&Fp12::from_base(c)))
+            })
+    }
+
+    fn degree() -> u32 {
+        12
+    }
+
+    fn base_order() -> Integer {
+        C::Base::modulus().clone()
+    }
+
+    fn characteristic() -> Integer {
+        C::Base::modulus().clone()
+    }
+
+    fn random_element() -> Self {
+        Fp12::new((0..12).map(|_| Fq::<C::Base>::random_element().0).collect())
+    }
+}
+
+std::thread_local! {
+    static DIVISIONS: Cell<u64> = const { Cell::new(0) };
+    static INVERSIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+// Numbers of divisions and inversions of Counted elements on this thread since the last call
+pub(crate) fn take_op_counts() -> (u64, u64) {
+    (DIVISIONS.with(Cell::take), INVERSIONS.with(Cell::take))
+}
+
+// Field F, counting its divisions and inversions to measure the cost of the group law: an
+// affine addition takes two divisions, and a doubling one inversion
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Counted<F>(pub(crate) F);
+
+impl<F: Field + Clone + PartialEq> Field for Counted<F> {
+    fn zero() -> Self {
+        Counted(F::zero())
+    }
+
+    fn one() -> Self {
+        Counted(F::one())
+    }
+
+    fn ct_eq(&self, y: &Self) -> bool {
+        self.0.ct_eq(&y.0)
+    }
+
+    fn add(self, y: &Self) -> Self {
+        Counted(self.0.add(&y.0))
+    }
+
+    fn mul(self, y: &Self) -> Self {
+        Counted(self.0.mul(&y.0))
+    }
+
+    fn zmul(self, y: i64) -> Self {
+        Counted(self.0.zmul(y))
+    }
+
+    fn pow(self, y: &Integer) -> Self {
+        Counted(self.0.pow(y))
+    }
+
+    fn zpow(self, y: i64) -> Self {
+        Counted(self.0.zpow(y))
+    }
+
+    fn div(self, y: &Self) -> Self {
+        DIVISIONS.with(|c| c.set(c.get() + 1));
+        Counted(self.0.div(&y.0))
+    }
+
+    fn square(self) -> Self {
+        Counted(self.0.square())
+    }
+
+    fn sqrt(self) -> Self {
+        Counted(self.0.sqrt())
+    }
+
+    fn invert(self) -> Self {
+        INVERSIONS.with(|c| c.set(c.get() + 1));
+        Counted(self.0.invert())
+    }
+
+    fn neg(self) -> Self {
+        Counted(self.0.neg())
+    }
+
+    fn degree() -> u32 {
+        F::degree()
+    }
+
+    fn base_order() -> Integer {
+        F::base_order()
+    }
+
+    fn characteristic() -> Integer {
+        F::characteristic()
+    }
+
+    fn random_element() -> Self {
+        Counted(F::random_element())
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{
+    elliptic_curve::{ECPoint, EllipticCurve},
+    errors::ErrorKind,
+    field::Field,
+};
+
+// Twist of a short Weierstrass curve E: y² = x³ + a4 x + a6 over Fq^k
+// E': y² = x³ + a4/w⁴ x + a6/w⁶ is isomorphic to E through (x, y) ↦ (w² x, w³ y),
+// and is defined over the smaller field Fq^(k/d) when w^d is in Fq^(k/d), d = degree
+#[derive(Clone, Debug, PartialEq)]
+pub struct Twist<F: Field> {
+    degree: u32,
+    w: F,
+}
+
+impl<F: Field + Clone + PartialEq> Twist<F> {
+    // New twist of degree d with twisting element w
+    // /!\ I'm not checking that w^d lies in the field of definition of the twist
+    pub fn new(degree: u32, w: F) -> Result<Self, ErrorKind> {
+        if ![2, 3, 4, 6].contains(&degree) {
+            return Err(ErrorKind::InvalidInput("Twist degree must be 2, 3, 4 or 6"));
+        }
+        if w == F::zero() {
+            return Err(ErrorKind::InvalidInput("Twisting element must not be zero"));
+        }
+        Ok(Twist { degree, w })
+    }
+
+    // Sextic twist for a tower built on the non-residue ξ, e.g. Fq6 = Fq2[v]/(v³ - ξ) and
+    // Fq12 = Fq6[w]/(w² - v), where w⁶ = ξ
+    // w is taken as a sixth root of ξ in F, so that untwisting scales x by w² = ξ^(1/3) and y by
+    // w³ = ξ^(1/2). Fails with NonResidue if ξ has no sixth root in F
+    pub fn sextic(xi: &F) -> Result<Self, ErrorKind> {
+        let w = xi.nth_root(6)?;
+        Self::new(6, w)
+    }
+
+    // Degree of the twist
+    pub fn degree(&self) -> u32 {
+        self.degree
+    }
+
+    // Returns the twisted curve E': y² = x³ + a4/w⁴ x + a6/w⁶
+    // Cubic and sextic twists need a4 = 0, quartic twists need a6 = 0
+    pub fn twisted_curve(&self, curve: &EllipticCurve<F>) -> Result<EllipticCurve<F>, ErrorKind> {
+        let (a1, a2, a3, a4, _, a6) = curve.get_a_invariants();
+        if *a1 != F::zero() || *a2 != F::zero() || *a3 != F::zero() {
+            return Err(ErrorKind::InvalidInput(
+                "Twists are only supported for short Weierstrass curves",
+            ));
+        }
+        if (self.degree.is_multiple_of(3) && *a4 != F::zero())
+            || (self.degree == 4 && *a6 != F::zero())
+        {
+            return Err(ErrorKind::InvalidInput(
+                "Curve does not admit a twist of this degree",
+            ));
+        }
+
+        let w2_inv = self.w.clone().square().invert();
+        let w6_inv = w2_inv.clone().zpow(3);
+        Ok(EllipticCurve::new_long_weierstrass((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            a4.clone().mul(&w2_inv.square()),
+            F::zero(),
+            a6.clone().mul(&w6_inv),
+        )))
+    }
+
+    // Untwisting isomorphism E' -> E, (x, y) ↦ (w² x, w³ y)
+    // The coordinates of the twisted point live in a subfield G of F, embedded into F with `embed`
+    pub fn untwist<G: Field + Clone>(
+        &self,
+        pt: &ECPoint<G>,
+        embed: impl Fn(&G) -> F,
+    ) -> ECPoint<F> {
+        match pt {
+            ECPoint::PointAtInfinity => ECPoint::PointAtInfinity,
+            ECPoint::AffinePoint(x, y) => {
+                let w2 = self.w.clone().square();
+                let w3 = w2.clone().mul(&self.w);
+                ECPoint::AffinePoint(embed(x).mul(&w2), embed(y).mul(&w3))
+            }
+        }
+    }
+
+    // Twisting isomorphism E -> E', (x, y) ↦ (x / w², y / w³)
+    pub fn twist(&self, pt: &ECPoint<F>) -> Result<ECPoint<F>, ErrorKind> {
+        match pt {
+            ECPoint::PointAtInfinity => Ok(ECPoint::PointAtInfinity),
+            ECPoint::AffinePoint(x, y) => {
+                let w2 = self.w.clone().square();
+                let w3 = w2.clone().mul(&self.w);
+                let zero_w = ErrorKind::InvalidInput("Twisting element must not be zero");
+                Ok(ECPoint::AffinePoint(
+                    x.checked_div(&w2).ok_or(zero_w.clone())?,
+                    y.checked_div(&w3).ok_or(zero_w)?,
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pairings::tate_pairing,
+        test_fields::{Fp, Fp2},
+    };
+    use rug::Integer;
+
+    type K = Fp2<103>;
+
+    // Supersingular y² = x³ + x over F, 104 = 8 × 13 points over F103
+    fn curve<F: Field + Clone + PartialEq>() -> EllipticCurve<F> {
+        EllipticCurve::new((
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            F::one(),
+            F::zero(),
+            F::zero(),
+        ))
+        .unwrap()
+    }
+
+    fn embed(x: &Fp<103>) -> K {
+        Fp2(x.clone(), Fp::zero())
+    }
+
+    // Point of order 13 over F103, the first abscissa from `seed` on
+    fn base_point(seed: i64) -> ECPoint<Fp<103>> {
+        (seed..)
+            .find_map(|x| {
+                let x = Fp::<103>::new(x);
+                let y = x.clone().square().add(&Fp::one()).mul(&x).sqrt();
+                let pt = curve().point(x, y).ok()?;
+                let pt = curve().mul(&Integer::from(8), &pt).ok()?;
+                (pt != ECPoint::PointAtInfinity).then_some(pt)
+            })
+            .unwrap()
+    }
+
+    // Distortion map (x, y) ↦ (-x, iy)
+    fn distort(pt: &ECPoint<Fp<103>>) -> ECPoint<K> {
+        let (x, y) = pt.xy().unwrap();
+        ECPoint::AffinePoint(embed(x).neg(), embed(y).mul(&K::new(0, 1)))
+    }
+
+    // With w = -i, w⁴ = 1 and the quadratic twist of y² = x³ + x is the curve itself, untwisting
+    // is then the distortion map
+    #[test]
+    fn quadratic_twist_round_trip() {
+        let curve = curve::<K>();
+        let twist = Twist::new(2, K::new(0, -1)).unwrap();
+        assert_eq!(twist.degree(), 2);
+        let twisted = twist.twisted_curve(&curve).unwrap();
+        assert_eq!(twisted, curve);
+
+        for seed in [1, 10, 50] {
+            let pt = base_point(seed);
+            let untwisted = twist.untwist(&pt, embed);
+            assert!(curve.is_on_curve(&untwisted));
+            assert_eq!(untwisted, distort(&pt));
+            assert_eq!(twist.twist(&untwisted), Ok(pt.embed(embed)));
+
+            let pt = curve.random_point().unwrap();
+            let twisted_pt = twist.twist(&pt).unwrap();
+            assert!(twisted.is_on_curve(&twisted_pt));
+            assert_eq!(twist.untwist(&twisted_pt, K::clone), pt);
+        }
+        assert_eq!(
+            twist.untwist(&ECPoint::PointAtInfinity, embed),
+            ECPoint::PointAtInfinity
+        );
+        assert!(Twist::new(5, K::one()).is_err());
+        assert!(Twist::new(2, K::zero()).is_err());
+    }
+
+    // E'(F103) untwists onto the second pairing group: the pairing through the twist is the one
+    // with the distorted point, is non-degenerate and linear in the twisted point
+    #[test]
+    fn pairing_through_quadratic_twist() {
+        let twist = Twist::new(2, K::new(0, -1)).unwrap();
+        let r = Integer::from(13);
+        let pt_p = base_point(3).embed(embed);
+        let pt_q = base_point(5);
+        let pt_q3 = curve().mul(&Integer::from(3), &pt_q).unwrap();
+        let curve = curve::<K>();
+        let pair = |pt_q: &ECPoint<K>| tate_pairing(&curve, &pt_p, pt_q, &r, 2).unwrap();
+
+        let e = pair(&twist.untwist(&pt_q, embed));
+        assert!(!e.is_one());
+        assert_eq!(e, pair(&distort(&pt_q)));
+        assert_eq!(
+            pair(&twist.untwist(&pt_q3, embed)),
+            e.pow(&Integer::from(3))
+        );
+    }
+
+    // y² = x³ + 2 has 117 = 9 × 13 points over F103 and 169 × 63 over K, with k = 2 for r = 13
+    // For ξ = c⁶ with c in K, E': y² = x³ + 2/ξ is isomorphic to E over K, and the Tate pairing
+    // is preserved by the twisting isomorphism
+    #[test]
+    fn sextic_twist_preserves_tate_pairing() {
+        fn curve_b2<F: Field + Clone + PartialEq>() -> EllipticCurve<F> {
+            EllipticCurve::new((
+                F::zero(),
+                F::zero(),
+                F::zero(),
+                F::zero(),
+                F::zero(),
+                F::one().double(),
+            ))
+            .unwrap()
+        }
+        let r = Integer::from(13);
+        let xi = K::new(2, 3).zpow(6);
+        let twist = Twist::sextic(&xi).unwrap();
+        assert_eq!(twist.degree(), 6);
+        assert_eq!(twist.w.clone().zpow(6), xi);
+
+        let curve_k = curve_b2::<K>();
+        let twisted = twist.twisted_curve(&curve_k).unwrap();
+        assert_eq!(
+            twisted,
+            EllipticCurve::new_long_weierstrass((
+                K::zero(),
+                K::zero(),
+                K::zero(),
+                K::zero(),
+                K::zero(),
+                K::one().double().div(&xi),
+            ))
+        );
+
+        // P in E(F103)[13] and Q in E(K)[13], with a non-trivial pairing
+        let pt_p = loop {
+            let pt = curve_b2::<Fp<103>>().random_point().unwrap();
+            let pt = curve_b2().mul(&Integer::from(9), &pt).unwrap();
+            if pt != ECPoint::PointAtInfinity {
+                break pt.embed(embed);
+            }
+        };
+        let (pt_q, e) = loop {
+            let pt = curve_k.random_point().unwrap();
+            let pt = curve_k.mul(&Integer::from(63), &pt).unwrap();
+            if pt == ECPoint::PointAtInfinity {
+                continue;
+            }
+            let e = tate_pairing(&curve_k, &pt_p, &pt, &r, 2).unwrap();
+            if !e.is_one() {
+                break (pt, e);
+            }
+        };
+
+        for pt in [&pt_p, &pt_q] {
+            let twisted_pt = twist.twist(pt).unwrap();
+            assert!(twisted.is_on_curve(&twisted_pt));
+            assert_eq!(twist.untwist(&twisted_pt, K::clone), *pt);
+        }
+        let twisted_p = twist.twist(&pt_p).unwrap();
+        let twisted_q = twist.twist(&pt_q).unwrap();
+        assert_eq!(tate_pairing(&twisted, &twisted_p, &twisted_q, &r, 2), Ok(e));
+    }
+}